@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate criterion;
+extern crate sliding_windows;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use sliding_windows::internals::Ring;
+use sliding_windows::{Adaptor, IterExt, Storage};
+use std::hint::black_box;
+
+const LEN: usize = 1 << 16;
+const WINDOW_SIZES: &[usize] = &[2, 10, 100, 1000];
+
+// visits every element of every window, the same work for both implementations
+fn sweep_window_sizes<T: Clone + Default>(c: &mut Criterion, name: &str, elt: T) {
+    let data = vec![elt; LEN];
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(LEN as u64));
+
+    for &window_size in WINDOW_SIZES {
+        let mut storage: Storage<&T> = Storage::new(window_size);
+        group.bench_function(BenchmarkId::new("sliding_windows", window_size), |b| {
+            b.iter(|| {
+                for window in Adaptor::new(black_box(&data).iter(), &mut storage) {
+                    for x in window.iter() {
+                        black_box(x);
+                    }
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("slice_windows", window_size), &data, |b, data| {
+            b.iter(|| {
+                for window in black_box(data).windows(window_size) {
+                    for x in window.iter() {
+                        black_box(x);
+                    }
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn element_types(c: &mut Criterion) {
+    sweep_window_sizes(c, "u8", 12u8);
+    sweep_window_sizes(c, "u64", 12u64);
+    sweep_window_sizes(c, "string", String::from("twelve"));
+}
+
+// input shorter than the window, which yields a single partial window
+fn short_input(c: &mut Criterion) {
+    let mut group = c.benchmark_group("short_input");
+    let mut storage: Storage<u64> = Storage::new(1024);
+
+    for &len in &[16u64, 512, 1023] {
+        group.bench_with_input(BenchmarkId::from_parameter(len), &len, |b, &len| {
+            b.iter(|| (0..black_box(len)).sliding_windows(&mut storage).count())
+        });
+    }
+
+    group.finish();
+}
+
+// only the internal ring buffer of the statistics adaptors, without any adaptor around it
+fn ring(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ring");
+    group.throughput(Throughput::Elements(LEN as u64));
+
+    for &window_size in WINDOW_SIZES {
+        group.bench_with_input(BenchmarkId::from_parameter(window_size), &window_size, |b, &window_size| {
+            b.iter(|| {
+                let mut ring = Ring::new(window_size);
+                for x in 0..LEN as u64 {
+                    black_box(ring.push(black_box(x)));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, element_types, short_input, ring);
+criterion_main!(benches);
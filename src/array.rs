@@ -0,0 +1,208 @@
+use std::array;
+use std::cell::{Cell, UnsafeCell};
+use std::mem::MaybeUninit;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::ptr;
+use std::slice;
+
+use ring::Ring;
+use sliding_windows::{AsWindowStorage, Window};
+
+/// A storage for windows of `N` elements in an inline array, which never allocates.
+///
+/// Unlike [FixedStorage](struct.FixedStorage.html), which fixes the window size of a
+/// heap allocated `Storage`, the elements live inside the `ArrayStorage` itself, e.g. on the
+/// stack or in a `static` buffer of an embedded target. Use it with
+/// [IterExt::sliding_windows](trait.IterExt.html#method.sliding_windows) like any other storage.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::{ArrayStorage, IterExt};
+///
+/// let mut storage: ArrayStorage<u32, 3> = ArrayStorage::new();
+/// let sums: Vec<u32> = (0..5).sliding_windows(&mut storage).map(|w| w.iter().sum()).collect();
+///
+/// assert_eq!(sums, [3, 6, 9]);
+/// ```
+pub struct ArrayStorage<T, const N: usize> {
+    // this is the offset of the first element
+    window_offset: Cell<usize>,
+    // the number of initialized elements, which are the first `len` elements of `data`
+    len: Cell<usize>,
+    // false while a window is alive
+    uniquely_owned: Cell<bool>,
+    // set when a panic unwound through an Adaptor using this storage
+    poisoned: Cell<bool>,
+    data: UnsafeCell<[MaybeUninit<T>; N]>,
+}
+
+impl<T, const N: usize> ArrayStorage<T, N> {
+    /// Create a new, empty `ArrayStorage`.
+    pub fn new() -> ArrayStorage<T, N> {
+        ArrayStorage {
+            window_offset: Cell::new(0),
+            len: Cell::new(0),
+            uniquely_owned: Cell::new(true),
+            poisoned: Cell::new(false),
+            data: UnsafeCell::new([(); N].map(|_| MaybeUninit::uninit())),
+        }
+    }
+
+    /// The number of buffered elements.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// See [Storage::is_poisoned](struct.Storage.html#method.is_poisoned).
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.get()
+    }
+
+    /// See [Storage::clear_poison](struct.Storage.html#method.clear_poison).
+    pub fn clear_poison(&mut self) {
+        AsWindowStorage::clear(&*self);
+        self.poisoned.set(false);
+    }
+
+    fn assert_no_window(&self) {
+        assert!(self.uniquely_owned.get(),
+                "next() called before previous Window went out of scope (array storage of {} elements)", N);
+    }
+
+    fn as_mut_ptr(&self) -> *mut T {
+        self.data.get() as *mut T
+    }
+
+    // drops the buffered elements, leaving the storage empty even if a drop panics
+    fn drop_elements(&self) {
+        let len = self.len.replace(0);
+        self.window_offset.set(0);
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), len)) };
+    }
+}
+
+impl<T, const N: usize> Default for ArrayStorage<T, N> {
+    fn default() -> ArrayStorage<T, N> {
+        ArrayStorage::new()
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayStorage<T, N> {
+    fn drop(&mut self) {
+        self.drop_elements();
+    }
+}
+
+// see the impls for Storage
+impl<T: UnwindSafe, const N: usize> UnwindSafe for ArrayStorage<T, N> {}
+impl<T: RefUnwindSafe, const N: usize> RefUnwindSafe for ArrayStorage<T, N> {}
+
+impl<T, const N: usize> AsWindowStorage<T> for ArrayStorage<T, N> {
+    fn window_size(&self) -> usize {
+        N
+    }
+
+    fn clear(&self) {
+        self.assert_no_window();
+        self.drop_elements();
+    }
+
+    fn push(&self, elt: T) -> bool {
+        self.assert_no_window();
+        if N == 0 {
+            return false;
+        }
+
+        let len = self.len.get();
+        if len < N {
+            unsafe { self.as_mut_ptr().add(len).write(elt) };
+            self.len.set(len + 1);
+            return len + 1 == N;
+        }
+
+        // the storage is full, overwrite the oldest element
+        let window_offset = self.window_offset.get();
+        let evicted = unsafe { ptr::replace(self.as_mut_ptr().add(window_offset), elt) };
+        self.window_offset.set(if window_offset + 1 == N { 0 } else { window_offset + 1 });
+        drop(evicted);
+        true
+    }
+
+    fn new_window(&self) -> Window<'_, T> {
+        self.assert_no_window();
+        let data = unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len.get()) };
+        self.uniquely_owned.set(false);
+        Window::from_parts(&self.uniquely_owned, &self.window_offset, data, self.window_offset.get(), N)
+    }
+
+    fn poison(&self) {
+        self.poisoned.set(true);
+    }
+
+    fn assert_not_poisoned(&self) {
+        assert!(!self.poisoned.get(),
+                "storage poisoned by a panic in an earlier Adaptor, call clear_poison() to reuse it (array storage of {} elements)", N);
+    }
+
+    fn assert_no_window(&self) {
+        ArrayStorage::assert_no_window(self)
+    }
+
+    fn new_suffix_window(&self, fill: usize) -> Option<Window<'_, T>> {
+        self.assert_no_window();
+        let data = unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len.get()) };
+        data.rotate_left(self.window_offset.get());
+        self.window_offset.set(0);
+        let start = data.len() - fill.min(data.len());
+        self.uniquely_owned.set(false);
+        Some(Window::from_parts(&self.uniquely_owned, &self.window_offset, &mut data[start..], 0, N))
+    }
+}
+
+/// Iterator yielding sliding windows of `N` `Copy` elements as arrays.
+///
+/// See [IterExt::array_windows](trait.IterExt.html#method.array_windows) for more information.
+pub struct ArrayWindows<I: Iterator, const N: usize> {
+    iter: I,
+    window: Ring<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> ArrayWindows<I, N> where I::Item: Copy {
+    /// This creates a new ArrayWindows iterator. Usually you should be using
+    /// [IterExt::array_windows](trait.IterExt.html#method.array_windows).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `N` is zero.
+    pub fn new(iter: I) -> ArrayWindows<I, N> {
+        assert!(N > 0, "window size must be positive");
+        ArrayWindows {
+            iter,
+            window: Ring::new(N),
+        }
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for ArrayWindows<I, N> where I::Item: Copy {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<[I::Item; N]> {
+        for elt in &mut self.iter {
+            self.window.push(elt);
+            if self.window.is_full() {
+                let mut elts = self.window.iter();
+                return Some(array::from_fn(|_| *elts.next().expect("the window is full")));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
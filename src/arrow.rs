@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use arrow_array::builder::{ListBuilder, PrimitiveBuilder};
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, ArrowNativeTypeOp, PrimitiveArray, RecordBatch};
+use arrow_schema::{ArrowError, Field, Schema};
+
+use sliding_windows::{Adaptor, AsWindowStorage, Window};
+
+impl<'a, T: ArrowNativeTypeOp> Window<'a, T> {
+    /// Copies the elements of the window in logical order into an Arrow array.
+    ///
+    /// Only available with the `arrow` feature.
+    ///
+    /// ```
+    /// # extern crate arrow_array;
+    /// # extern crate sliding_windows;
+    /// use arrow_array::types::UInt32Type;
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// # fn main() {
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let window = (0..5).sliding_windows(&mut storage).next().unwrap();
+    ///
+    /// assert_eq!(window.to_arrow_array::<UInt32Type>().values(), &[0, 1, 2]);
+    /// # }
+    /// ```
+    pub fn to_arrow_array<P>(&self) -> PrimitiveArray<P> where P: ArrowPrimitiveType<Native = T> {
+        PrimitiveArray::from_iter_values(self.iter().cloned())
+    }
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: ArrowNativeTypeOp {
+    /// Consumes the adaptor and collects all windows into a `RecordBatch` with a single
+    /// list column named `window`, holding one window per row.
+    ///
+    /// Only available with the `arrow` feature.
+    pub fn collect_record_batch<P>(self) -> Result<RecordBatch, ArrowError>
+        where P: ArrowPrimitiveType<Native = I::Item>
+    {
+        let mut builder = ListBuilder::new(PrimitiveBuilder::<P>::new());
+
+        for window in self {
+            for &x in &window {
+                builder.values().append_value(x);
+            }
+            builder.append(true);
+        }
+
+        let windows = builder.finish();
+        let schema = Schema::new(vec![Field::new("window", windows.data_type().clone(), false)]);
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(windows)])
+    }
+}
@@ -0,0 +1,174 @@
+use std::marker::PhantomData;
+
+/// The unsigned integer types [BitWindows](struct.BitWindows.html) yields its windows as.
+///
+/// Implemented for `u8`, `u16`, `u32`, `u64` and `u128`.
+pub trait BitMask: Copy {
+    /// The number of bits of the type, i.e. the largest supported window size.
+    const BITS: usize;
+
+    /// Truncates `bits` to the type.
+    fn from_u128(bits: u128) -> Self;
+}
+
+macro_rules! impl_bit_mask {
+    ($($t:ty),*) => {
+        $(
+            impl BitMask for $t {
+                const BITS: usize = <$t>::BITS as usize;
+
+                fn from_u128(bits: u128) -> $t {
+                    bits as $t
+                }
+            }
+        )*
+    }
+}
+
+impl_bit_mask!(u8, u16, u32, u64, u128);
+
+/// Iterator over the bits of a byte iterator, most significant bit first.
+///
+/// See [IterExt::bits](trait.IterExt.html#method.bits) for more information.
+pub struct Bits<I> {
+    iter: I,
+    byte: u8,
+    // bits of `byte` not yielded yet
+    remaining: u32,
+}
+
+impl<I: Iterator<Item = u8>> Bits<I> {
+    /// This creates a new Bits iterator. Usually you should be using
+    /// [IterExt::bits](trait.IterExt.html#method.bits).
+    pub fn new(iter: I) -> Bits<I> {
+        Bits { iter, byte: 0, remaining: 0 }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Bits<I> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.remaining == 0 {
+            self.byte = self.iter.next()?;
+            self.remaining = 8;
+        }
+        self.remaining -= 1;
+        Some((self.byte >> self.remaining) & 1 == 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let remaining = self.remaining as usize;
+        (lower.saturating_mul(8).saturating_add(remaining),
+         upper.and_then(|upper| upper.checked_mul(8)?.checked_add(remaining)))
+    }
+}
+
+/// Iterator yielding sliding windows over the bits of a byte iterator as integer masks.
+///
+/// See [IterExt::bit_windows](trait.IterExt.html#method.bit_windows) for more information.
+pub struct BitWindows<I, M> {
+    bits: Bits<I>,
+    window_size: usize,
+    // the most recent bits, the newest one is the least significant bit
+    register: u128,
+    // the number of bits in the register, up to the window size
+    filled: usize,
+    _mask: PhantomData<M>,
+}
+
+impl<I: Iterator<Item = u8>, M: BitMask> BitWindows<I, M> {
+    /// This creates a new BitWindows iterator. Usually you should be using
+    /// [IterExt::bit_windows](trait.IterExt.html#method.bit_windows).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `window_size` is zero or exceeds the number of bits of `M`.
+    pub fn new(iter: I, window_size: usize) -> BitWindows<I, M> {
+        assert!(window_size > 0 && window_size <= M::BITS,
+                "window size {} must be in 1..={}", window_size, M::BITS);
+        BitWindows {
+            bits: Bits::new(iter),
+            window_size,
+            register: 0,
+            filled: 0,
+            _mask: PhantomData,
+        }
+    }
+
+    fn mask(&self) -> u128 {
+        u128::MAX >> (128 - self.window_size)
+    }
+}
+
+impl<I: Iterator<Item = u8>, M: BitMask> Iterator for BitWindows<I, M> {
+    type Item = M;
+
+    fn next(&mut self) -> Option<M> {
+        loop {
+            let bit = self.bits.next()?;
+            self.register = ((self.register << 1) | bit as u128) & self.mask();
+            self.filled = self.window_size.min(self.filled + 1);
+            if self.filled == self.window_size {
+                return Some(M::from_u128(self.register));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // the number of bits still needed before the first window is full
+        let missing = self.window_size - self.filled.max(1);
+        let (lower, upper) = self.bits.size_hint();
+        (lower.saturating_sub(missing), upper.map(|upper| upper.saturating_sub(missing)))
+    }
+}
+
+/// Iterator yielding the bit offsets where a sync word starts.
+///
+/// See [IterExt::find_sync](trait.IterExt.html#method.find_sync) for more information.
+pub struct FindSync<I> {
+    windows: BitWindows<I, u128>,
+    pattern: u128,
+    max_bit_errors: u32,
+    // the bit offset of the next window
+    offset: usize,
+}
+
+impl<I: Iterator<Item = u8>> FindSync<I> {
+    /// This creates a new FindSync iterator. Usually you should be using
+    /// [IterExt::find_sync](trait.IterExt.html#method.find_sync).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `pattern` is empty or longer than 16 bytes.
+    pub fn new(iter: I, pattern: &[u8], max_bit_errors: u32) -> FindSync<I> {
+        assert!(!pattern.is_empty() && pattern.len() <= 16,
+                "pattern length {} must be in 1..=16 bytes", pattern.len());
+        FindSync {
+            windows: BitWindows::new(iter, pattern.len() * 8),
+            pattern: pattern.iter().fold(0, |acc, &byte| (acc << 8) | byte as u128),
+            max_bit_errors,
+            offset: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for FindSync<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for window in &mut self.windows {
+            let offset = self.offset;
+            self.offset += 1;
+            if (window ^ self.pattern).count_ones() <= self.max_bit_errors {
+                return Some(offset);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.windows.size_hint().1)
+    }
+}
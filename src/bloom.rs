@@ -0,0 +1,188 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::mem;
+
+// a fixed size Bloom filter, the bit indices are derived from two hashes
+struct Bloom {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl Bloom {
+    fn new(num_bits: u64, num_hashes: u32) -> Bloom {
+        Bloom {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    // the word and the mask of the `i`th bit of an element (Kirsch-Mitzenmacher double hashing)
+    fn bit(&self, (h1, h2): (u64, u64), i: u32) -> (usize, u64) {
+        let index = h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.num_bits;
+        ((index / 64) as usize, 1 << (index % 64))
+    }
+
+    fn insert(&mut self, hashes: (u64, u64)) {
+        for i in 0..self.num_hashes {
+            let (word, mask) = self.bit(hashes, i);
+            self.bits[word] |= mask;
+        }
+    }
+
+    fn contains(&self, hashes: (u64, u64)) -> bool {
+        (0..self.num_hashes).all(|i| {
+            let (word, mask) = self.bit(hashes, i);
+            self.bits[word] & mask != 0
+        })
+    }
+
+    fn clear(&mut self) {
+        for word in &mut self.bits {
+            *word = 0;
+        }
+    }
+}
+
+/// An approximate [RecentSet](struct.RecentSet.html) backed by a rotating pair of Bloom
+/// filters, for windows too large to track exactly.
+///
+/// The memory use only depends on the window size and the false positive rate, not on the
+/// size of the values. Values of the last `window_size` pushes are always found. Other
+/// values are reported as contained with roughly the configured false positive rate, and
+/// values pushed up to `2 * window_size` pushes ago may still be found, since the older
+/// filter is only discarded every `window_size` pushes.
+///
+/// Only available with the `bloom` feature.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::ApproxRecentSet;
+///
+/// let mut recent = ApproxRecentSet::new(1000, 0.01);
+/// for id in 0..1000u32 {
+///     recent.push(&id);
+/// }
+///
+/// assert!(recent.contains(&999));
+/// assert!((0..1000).all(|id| recent.contains(&id)));
+/// ```
+pub struct ApproxRecentSet<T: ?Sized> {
+    window_size: usize,
+    current: Bloom,
+    previous: Bloom,
+    // values pushed into `current`
+    pushed: usize,
+    hashers: (RandomState, RandomState),
+    _value: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> ApproxRecentSet<T> {
+    /// Create an empty `ApproxRecentSet` over the last `window_size` values, reporting values
+    /// which were never pushed with probability `false_positive_rate`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `false_positive_rate` is not in the open interval `(0, 1)`.
+    pub fn new(window_size: usize, false_positive_rate: f64) -> ApproxRecentSet<T> {
+        assert!(false_positive_rate > 0.0 && false_positive_rate < 1.0,
+                "false positive rate {} must be in (0, 1)", false_positive_rate);
+
+        // both filters are queried, so each gets half of the false positive rate
+        let rate = false_positive_rate / 2.0;
+        let ln2 = ::std::f64::consts::LN_2;
+        let n = window_size.max(1) as f64;
+        let num_bits = (-n * rate.ln() / (ln2 * ln2)).ceil().max(64.0);
+        let num_hashes = (num_bits / n * ln2).round().max(1.0);
+
+        ApproxRecentSet {
+            window_size,
+            current: Bloom::new(num_bits as u64, num_hashes as u32),
+            previous: Bloom::new(num_bits as u64, num_hashes as u32),
+            pushed: 0,
+            hashers: (RandomState::new(), RandomState::new()),
+            _value: PhantomData,
+        }
+    }
+
+    /// The number of values the set is guaranteed to remember.
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Whether `value` is probably one of the recently pushed values. Never `false` for one
+    /// of the last `window_size` values.
+    pub fn contains(&self, value: &T) -> bool {
+        if self.window_size == 0 {
+            return false;
+        }
+        let hashes = self.hashes(value);
+        self.current.contains(hashes) || self.previous.contains(hashes)
+    }
+
+    /// Push `value` as the most recent value. Returns whether `value` was probably pushed
+    /// recently before, like [contains](#method.contains).
+    pub fn push(&mut self, value: &T) -> bool {
+        if self.window_size == 0 {
+            return false;
+        }
+        let hashes = self.hashes(value);
+        let seen = self.current.contains(hashes) || self.previous.contains(hashes);
+
+        if self.pushed == self.window_size {
+            mem::swap(&mut self.current, &mut self.previous);
+            self.current.clear();
+            self.pushed = 0;
+        }
+        self.current.insert(hashes);
+        self.pushed += 1;
+
+        seen
+    }
+
+    fn hashes(&self, value: &T) -> (u64, u64) {
+        // a zero step would map every hash function to the same bit
+        (self.hashers.0.hash_one(value), self.hashers.1.hash_one(value) | 1)
+    }
+}
+
+/// Iterator yielding the elements of the wrapped iterator which were probably not among the
+/// preceding `window_size` elements.
+///
+/// See [IterExt::dedup_within_approx](trait.IterExt.html#method.dedup_within_approx) for more information.
+pub struct ApproxDedupWithin<I: Iterator> {
+    iter: I,
+    recent: ApproxRecentSet<I::Item>,
+}
+
+impl<I: Iterator> ApproxDedupWithin<I> where I::Item: Hash {
+    /// This creates a new ApproxDedupWithin iterator. Usually you should be using
+    /// [IterExt::dedup_within_approx](trait.IterExt.html#method.dedup_within_approx).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `false_positive_rate` is not in the open interval `(0, 1)`.
+    pub fn new(iter: I, window_size: usize, false_positive_rate: f64) -> ApproxDedupWithin<I> {
+        ApproxDedupWithin {
+            iter,
+            recent: ApproxRecentSet::new(window_size, false_positive_rate),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for ApproxDedupWithin<I> where I::Item: Hash {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let recent = &mut self.recent;
+        self.iter.find(|elt| !recent.push(elt))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // any element may be a duplicate or a false positive
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -0,0 +1,102 @@
+use sliding_windows::{Storage, Window};
+
+type Callback<'a, T> = Box<dyn FnMut(&Window<'_, T>) + 'a>;
+
+struct Consumer<'a, T> {
+    storage: Storage<T>,
+    callback: Callback<'a, T>,
+    // whether the callback saw a full window
+    called: bool,
+}
+
+/// Feeds one pass over a stream to several window consumers, each with its own window size.
+///
+/// Every consumer buffers clones of the elements in its own `Storage`, so expensive sources
+/// like files or network streams are read only once. Consumers behave like an
+/// [Adaptor](struct.Adaptor.html): they are called for every full window, or once with a
+/// partial window if the stream is shorter than their window.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::WindowBus;
+///
+/// let mut short_means = Vec::new();
+/// let mut long_maxima = Vec::new();
+///
+/// let consumed = WindowBus::new()
+///     .subscribe(2, |window| short_means.push(window.iter().sum::<u32>() / 2))
+///     .subscribe(4, |window| long_maxima.push(*window.iter().max().unwrap()))
+///     .run(vec![4, 2, 8, 6, 0]);
+///
+/// assert_eq!(consumed, 5);
+/// assert_eq!(short_means, [3, 5, 7, 3]);
+/// assert_eq!(long_maxima, [8, 8]);
+/// ```
+pub struct WindowBus<'a, T> {
+    consumers: Vec<Consumer<'a, T>>,
+}
+
+impl<'a, T: Clone> WindowBus<'a, T> {
+    /// Create a `WindowBus` without consumers.
+    pub fn new() -> WindowBus<'a, T> {
+        WindowBus { consumers: Vec::new() }
+    }
+
+    /// Add a consumer which is called with every window of `window_size` elements.
+    /// Consumers with window size 0 are never called.
+    pub fn subscribe<F>(mut self, window_size: usize, callback: F) -> WindowBus<'a, T>
+        where F: FnMut(&Window<'_, T>) + 'a
+    {
+        self.consumers.push(Consumer {
+            storage: Storage::new(window_size),
+            callback: Box::new(callback),
+            called: false,
+        });
+        self
+    }
+
+    /// Feed all elements of `iter` to the consumers and return the number of elements.
+    pub fn run<I>(mut self, iter: I) -> usize where I: IntoIterator<Item = T> {
+        let mut count = 0;
+
+        for elt in iter {
+            count += 1;
+            let (last, rest) = match self.consumers.split_last_mut() {
+                Some(split) => split,
+                None => continue,
+            };
+
+            for consumer in rest {
+                consumer.push(elt.clone());
+            }
+            last.push(elt);
+        }
+
+        // like an Adaptor, a too short input yields one partial window
+        for consumer in &mut self.consumers {
+            if !consumer.called && consumer.storage.len() > 0 {
+                let window = consumer.storage.new_window();
+                (consumer.callback)(&window);
+            }
+        }
+
+        count
+    }
+}
+
+impl<'a, T> Consumer<'a, T> {
+    fn push(&mut self, elt: T) {
+        if self.storage.window_size() > 0 && self.storage.push(elt) {
+            self.called = true;
+            let window = self.storage.new_window();
+            (self.callback)(&window);
+        }
+    }
+}
+
+impl<'a, T: Clone> Default for WindowBus<'a, T> {
+    fn default() -> WindowBus<'a, T> {
+        WindowBus::new()
+    }
+}
@@ -0,0 +1,66 @@
+use std::convert::TryInto;
+use std::slice;
+
+/// Iterator yielding the windows of `N` bytes of a byte slice as array references.
+///
+/// See [byte_windows](fn.byte_windows.html) for more information.
+pub struct ByteWindows<'a, const N: usize> {
+    windows: slice::Windows<'a, u8>,
+}
+
+// the length of every window is N
+fn as_array<const N: usize>(window: &[u8]) -> &[u8; N] {
+    window.try_into().expect("windows hold N bytes")
+}
+
+impl<'a, const N: usize> Iterator for ByteWindows<'a, N> {
+    type Item = &'a [u8; N];
+
+    fn next(&mut self) -> Option<&'a [u8; N]> {
+        self.windows.next().map(as_array)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'a [u8; N]> {
+        self.windows.nth(n).map(as_array)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<'a, const N: usize> DoubleEndedIterator for ByteWindows<'a, N> {
+    fn next_back(&mut self) -> Option<&'a [u8; N]> {
+        self.windows.next_back().map(as_array)
+    }
+}
+
+impl<'a, const N: usize> ExactSizeIterator for ByteWindows<'a, N> {}
+
+/// Yields every window of `N` bytes of `bytes` as a `&[u8; N]` pointing into `bytes`.
+///
+/// This is the fast path for scanning contiguous input, e.g. a memory mapped file, for
+/// fixed-size protocol headers: nothing is copied into a `Storage`, and the array length lets
+/// fields be read with `from_be_bytes` or pattern matching without bounds checks.
+///
+/// ```
+/// use sliding_windows::byte_windows;
+///
+/// // a 4 byte magic number followed by a 2 byte big-endian length, after some noise
+/// let stream = [0x00, 0xff, 0xca, 0xfe, 0xba, 0xbe, 0x00, 0x10];
+/// let length = byte_windows::<6>(&stream)
+///     .find(|header| u32::from_be_bytes([header[0], header[1], header[2], header[3]]) == 0xcafe_babe)
+///     .map(|&[_, _, _, _, hi, lo]| u16::from_be_bytes([hi, lo]));
+///
+/// assert_eq!(length, Some(16));
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `N` is zero.
+pub fn byte_windows<const N: usize>(bytes: &[u8]) -> ByteWindows<'_, N> {
+    assert!(N > 0, "window size must be positive");
+    ByteWindows {
+        windows: bytes.windows(N),
+    }
+}
@@ -0,0 +1,68 @@
+/// Sliding windows of `window_size` chars as `&str`, see
+/// [IterExt::char_windows](trait.IterExt.html#method.char_windows).
+///
+/// The windows are slices of one reused `String`, so this is not an `Iterator`: each window
+/// borrows the `CharWindows` until the next call of [next_window](#method.next_window).
+pub struct CharWindows<I> {
+    iter: I,
+    window_size: usize,
+    buf: String,
+    // the byte offset of the window in `buf`, the bytes before it are evicted chars
+    start: usize,
+    // the number of chars in the window
+    len: usize,
+    yielded: bool,
+    done: bool,
+}
+
+impl<I: Iterator<Item = char>> CharWindows<I> {
+    /// This creates a new CharWindows. Usually you should be using
+    /// [IterExt::char_windows](trait.IterExt.html#method.char_windows).
+    pub fn new(iter: I, window_size: usize) -> CharWindows<I> {
+        CharWindows {
+            iter,
+            window_size,
+            buf: String::with_capacity(window_size),
+            start: 0,
+            len: 0,
+            yielded: false,
+            done: false,
+        }
+    }
+
+    /// The next window, or `None` if the input ended. Like an `Adaptor`, a single shorter
+    /// window is yielded if the input is shorter than the window size.
+    pub fn next_window(&mut self) -> Option<&str> {
+        if self.done || self.window_size == 0 {
+            return None;
+        }
+
+        for c in &mut self.iter {
+            if self.len == self.window_size {
+                let oldest = self.buf[self.start..].chars().next().expect("a full window is not empty");
+                self.start += oldest.len_utf8();
+                self.len -= 1;
+                // move the window to the front once it is shorter than the evicted bytes,
+                // which copies every byte a constant number of times on average
+                if self.start >= self.buf.len() - self.start {
+                    self.buf.drain(..self.start);
+                    self.start = 0;
+                }
+            }
+            self.buf.push(c);
+            self.len += 1;
+
+            if self.len == self.window_size {
+                self.yielded = true;
+                return Some(&self.buf[self.start..]);
+            }
+        }
+
+        self.done = true;
+        if !self.yielded && self.len > 0 {
+            self.yielded = true;
+            return Some(&self.buf[self.start..]);
+        }
+        None
+    }
+}
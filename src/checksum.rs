@@ -0,0 +1,145 @@
+use ring::Ring;
+
+const ADLER_MOD: u64 = 65521;
+// the reflected CRC-32 polynomial used by zlib, PNG, Ethernet, ...
+const CRC_POLY: u32 = 0xedb8_8320;
+
+/// Iterator yielding the Adler-32 checksum of every window of a byte stream.
+///
+/// See [IterExt::rolling_adler32](trait.IterExt.html#method.rolling_adler32) for more information.
+pub struct RollingAdler32<I> {
+    iter: I,
+    window: Ring<u8>,
+    a: u64,
+    b: u64,
+}
+
+impl<I: Iterator<Item = u8>> RollingAdler32<I> {
+    /// This creates a new RollingAdler32 iterator. Usually you should be using
+    /// [IterExt::rolling_adler32](trait.IterExt.html#method.rolling_adler32).
+    pub fn new(iter: I, window_size: usize) -> RollingAdler32<I> {
+        RollingAdler32 {
+            iter,
+            window: Ring::new(window_size),
+            a: 1,
+            b: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for RollingAdler32<I> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let window_size = self.window.capacity();
+        if window_size == 0 {
+            return None;
+        }
+        let n = window_size as u64 % ADLER_MOD;
+
+        for byte in &mut self.iter {
+            let y = byte as u64;
+            match self.window.push(byte) {
+                None => {
+                    self.a = (self.a + y) % ADLER_MOD;
+                    self.b = (self.b + self.a) % ADLER_MOD;
+                },
+                Some(old) => {
+                    // A' = A - x + y, B' = B - n * x + A' - 1
+                    let x = old as u64;
+                    self.a = (self.a + ADLER_MOD - x + y) % ADLER_MOD;
+                    self.b = (self.b + 2 * ADLER_MOD + self.a - 1 - n * x % ADLER_MOD) % ADLER_MOD;
+                },
+            }
+
+            if self.window.is_full() {
+                return Some((self.b << 16 | self.a) as u32);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// Iterator yielding the CRC-32 checksum of every window of a byte stream.
+///
+/// See [IterExt::rolling_crc32](trait.IterExt.html#method.rolling_crc32) for more information.
+pub struct RollingCrc32<I> {
+    iter: I,
+    window: Ring<u8>,
+    table: [u32; 256],
+    // the change of the register when the byte leaving the window is removed
+    out_table: [u32; 256],
+    register: u32,
+}
+
+impl<I: Iterator<Item = u8>> RollingCrc32<I> {
+    /// This creates a new RollingCrc32 iterator. Usually you should be using
+    /// [IterExt::rolling_crc32](trait.IterExt.html#method.rolling_crc32).
+    pub fn new(iter: I, window_size: usize) -> RollingCrc32<I> {
+        let mut table = [0; 256];
+        for (byte, entry) in table.iter_mut().enumerate() {
+            let mut crc = byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ CRC_POLY } else { crc >> 1 };
+            }
+            *entry = crc;
+        }
+
+        // The register is affine in the initial value and the data, so removing the oldest
+        // byte `x` from a register over `[x] ++ rest` flips `shift(step(init, x) ^ init)`,
+        // where `shift` feeds the `window_size` zero bytes of `rest`. `shift` is linear, so
+        // it is only evaluated for the constant part and once per bit of `x`.
+        let shift = |mut register: u32| {
+            for _ in 0..window_size {
+                register = (register >> 8) ^ table[register as usize & 0xff];
+            }
+            register
+        };
+        let init = u32::MAX;
+        let constant = shift(init ^ (init >> 8) ^ table[init as usize & 0xff]);
+        let bits: Vec<u32> = (0..8).map(|bit| shift(table[1 << bit])).collect();
+
+        let mut out_table = [0; 256];
+        for (byte, entry) in out_table.iter_mut().enumerate() {
+            *entry = (0..8).filter(|bit| byte >> bit & 1 == 1).fold(constant, |acc, bit| acc ^ bits[bit]);
+        }
+
+        RollingCrc32 {
+            iter,
+            window: Ring::new(window_size),
+            table,
+            out_table,
+            register: init,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for RollingCrc32<I> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for byte in &mut self.iter {
+            self.register = (self.register >> 8) ^ self.table[(self.register as u8 ^ byte) as usize];
+            if let Some(old) = self.window.push(byte) {
+                self.register ^= self.out_table[old as usize];
+            }
+
+            if self.window.is_full() {
+                return Some(!self.register);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
@@ -0,0 +1,112 @@
+use num::WindowNum;
+use ring::Ring;
+
+/// Iterator yielding a debounced boolean state for every element of the wrapped iterator.
+///
+/// See [IterExt::debounce](trait.IterExt.html#method.debounce) for more information.
+pub struct Debounce<I, F> {
+    iter: I,
+    predicate: F,
+    window: Ring<bool>,
+    quorum: usize,
+    // number of elements in the window satisfying the predicate
+    satisfied: usize,
+    state: bool,
+}
+
+impl<I: Iterator, F> Debounce<I, F> where F: FnMut(&I::Item) -> bool {
+    /// This creates a new Debounce iterator. Usually you should be using
+    /// [IterExt::debounce](trait.IterExt.html#method.debounce).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `quorum` is zero or larger than `window_size`.
+    pub fn new(iter: I, window_size: usize, quorum: usize, predicate: F) -> Debounce<I, F> {
+        assert!(quorum > 0 && quorum <= window_size, "quorum must be in 1..=window_size");
+
+        Debounce {
+            iter,
+            predicate,
+            window: Ring::new(window_size),
+            quorum,
+            satisfied: 0,
+            state: false,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for Debounce<I, F> where F: FnMut(&I::Item) -> bool {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let elt = self.iter.next()?;
+        let satisfied = (self.predicate)(&elt);
+
+        self.satisfied += satisfied as usize;
+        if let Some(true) = self.window.push(satisfied) {
+            self.satisfied -= 1;
+        }
+
+        if self.satisfied >= self.quorum {
+            self.state = true;
+        } else if self.window.len() - self.satisfied >= self.quorum {
+            self.state = false;
+        }
+
+        Some(self.state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator yielding a boolean state with hysteresis (a Schmitt trigger) for every element
+/// of the wrapped iterator.
+///
+/// See [IterExt::hysteresis](trait.IterExt.html#method.hysteresis) for more information.
+pub struct Hysteresis<I> {
+    iter: I,
+    low: f64,
+    high: f64,
+    state: bool,
+}
+
+impl<I: Iterator> Hysteresis<I> where I::Item: WindowNum {
+    /// This creates a new Hysteresis iterator. Usually you should be using
+    /// [IterExt::hysteresis](trait.IterExt.html#method.hysteresis).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `low` is larger than `high` or either is NaN.
+    pub fn new(iter: I, low: f64, high: f64) -> Hysteresis<I> {
+        assert!(low <= high, "low threshold {} must not exceed the high threshold {}", low, high);
+
+        Hysteresis {
+            iter,
+            low,
+            high,
+            state: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Hysteresis<I> where I::Item: WindowNum {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let x = self.iter.next()?.to_f64();
+
+        if x >= self.high {
+            self.state = true;
+        } else if x <= self.low {
+            self.state = false;
+        }
+
+        Some(self.state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
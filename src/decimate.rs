@@ -0,0 +1,51 @@
+/// Iterator yielding one reduced value per `n` elements of the wrapped iterator.
+///
+/// See [IterExt::decimate](trait.IterExt.html#method.decimate) for more information.
+pub struct Decimate<I: Iterator, F> {
+    iter: I,
+    n: usize,
+    reducer: F,
+    buffer: Vec<I::Item>,
+}
+
+impl<I: Iterator, U, F> Decimate<I, F> where F: FnMut(&[I::Item]) -> U {
+    /// This creates a new Decimate iterator. Usually you should be using
+    /// [IterExt::decimate](trait.IterExt.html#method.decimate).
+    pub fn new(iter: I, n: usize, reducer: F) -> Decimate<I, F> {
+        Decimate {
+            iter,
+            n,
+            reducer,
+            buffer: Vec::with_capacity(n),
+        }
+    }
+}
+
+impl<I: Iterator, U, F> Iterator for Decimate<I, F> where F: FnMut(&[I::Item]) -> U {
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        if self.n == 0 {
+            return None;
+        }
+
+        // the buffer is reused, so this doesn't allocate after the first call
+        self.buffer.clear();
+        self.buffer.extend((&mut self.iter).take(self.n));
+
+        if self.buffer.len() == self.n {
+            Some((self.reducer)(&self.buffer))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        (lower / self.n, upper.map(|upper| upper / self.n))
+    }
+}
@@ -0,0 +1,139 @@
+//! Change-point detection over trailing windows.
+//!
+//! Instead of windows, the [Detections](struct.Detections.html) iterator yields a
+//! [ChangePoint](struct.ChangePoint.html) for every element the configured
+//! [Detector](enum.Detector.html) flags.
+//!
+//! # Example:
+//!
+//! ```
+//! use sliding_windows::IterExt;
+//! use sliding_windows::detect::Detector;
+//!
+//! let data = [1.0, 1.1, 0.9, 1.0, 1.1, 0.9, 8.0, 1.0];
+//! let detector = Detector::ZScore { window_size: 5, threshold: 3.0 };
+//! let indices: Vec<usize> = data.iter().cloned().detect(detector).map(|c| c.index).collect();
+//!
+//! assert_eq!(indices, [6]);
+//! ```
+
+use num::WindowNum;
+use rolling::Moments;
+
+/// The statistic used to flag elements.
+///
+/// Both detectors compare every element against the mean and the sample standard deviation
+/// of the `window_size` elements preceding it, so no element is flagged before the first
+/// `window_size` elements were seen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Detector {
+    /// Flag every element whose z-score is larger than `threshold` in absolute value.
+    ZScore { window_size: usize, threshold: f64 },
+    /// Two-sided CUSUM of the z-scores: deviations larger than `drift` are accumulated and an
+    /// element is flagged once either cumulative sum exceeds `threshold`.
+    /// Both sums are reset after a change point was reported.
+    Cusum { window_size: usize, drift: f64, threshold: f64 },
+}
+
+impl Detector {
+    fn window_size(&self) -> usize {
+        match *self {
+            Detector::ZScore { window_size, .. } => window_size,
+            Detector::Cusum { window_size, .. } => window_size,
+        }
+    }
+}
+
+/// An element flagged by a `Detector`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangePoint {
+    /// The position of the element in the input.
+    pub index: usize,
+    /// The z-score of the element or the CUSUM statistic which exceeded the threshold.
+    /// It is negative for downward changes.
+    pub score: f64,
+}
+
+/// Iterator yielding the `ChangePoint`s of the wrapped iterator.
+///
+/// See [IterExt::detect](../trait.IterExt.html#method.detect) for more information.
+pub struct Detections<I> {
+    iter: I,
+    detector: Detector,
+    moments: Moments,
+    index: usize,
+    // cumulative sums of the CUSUM detector
+    upper: f64,
+    lower: f64,
+}
+
+impl<I: Iterator> Detections<I> where I::Item: WindowNum {
+    /// This creates a new Detections iterator. Usually you should be using
+    /// [IterExt::detect](../trait.IterExt.html#method.detect).
+    pub fn new(iter: I, detector: Detector) -> Detections<I> {
+        Detections {
+            iter,
+            detector,
+            moments: Moments::new(detector.window_size()),
+            index: 0,
+            upper: 0.0,
+            lower: 0.0,
+        }
+    }
+
+    // the score of x if it has to be reported
+    fn score(&mut self, x: f64) -> Option<f64> {
+        let z = (x - self.moments.mean()) / self.moments.variance().sqrt();
+
+        match self.detector {
+            // a constant window or one of less than two elements has no spread to compare
+            // against and produces infinite or NaN z-scores
+            _ if !z.is_finite() => None,
+            Detector::ZScore { threshold, .. } => {
+                if z.abs() > threshold { Some(z) } else { None }
+            },
+            Detector::Cusum { drift, threshold, .. } => {
+
+                self.upper = (self.upper + z - drift).max(0.0);
+                self.lower = (self.lower - z - drift).max(0.0);
+
+                let score = if self.upper > threshold {
+                    self.upper
+                } else if self.lower > threshold {
+                    -self.lower
+                } else {
+                    return None;
+                };
+
+                self.upper = 0.0;
+                self.lower = 0.0;
+                Some(score)
+            },
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Detections<I> where I::Item: WindowNum {
+    type Item = ChangePoint;
+
+    fn next(&mut self) -> Option<ChangePoint> {
+        while let Some(x) = self.iter.next() {
+            let x = x.to_f64();
+            let index = self.index;
+            self.index += 1;
+
+            let score = if self.moments.is_full() { self.score(x) } else { None };
+            self.moments.push(x);
+
+            if let Some(score) = score {
+                return Some(ChangePoint { index, score });
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
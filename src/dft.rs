@@ -0,0 +1,112 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use num::WindowNum;
+use ring::Ring;
+
+/// A complex number as yielded by `SlidingDft`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// The magnitude (absolute value).
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The phase in radians.
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    // e^(i * phi)
+    fn from_phase(phi: f64) -> Complex {
+        Complex::new(phi.cos(), phi.sin())
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(self.re * other.re - self.im * other.im, self.re * other.im + self.im * other.re)
+    }
+}
+
+/// Iterator yielding a single DFT bin of the trailing window.
+///
+/// See [IterExt::sliding_dft](trait.IterExt.html#method.sliding_dft) for more information.
+pub struct SlidingDft<I> {
+    iter: I,
+    bin: usize,
+    window: Ring<f64>,
+    // e^(2 * pi * i * bin / window_size), rotates the bin by one sample
+    twiddle: Complex,
+    value: Complex,
+}
+
+impl<I: Iterator> SlidingDft<I> where I::Item: WindowNum {
+    /// This creates a new SlidingDft iterator. Usually you should be using
+    /// [IterExt::sliding_dft](trait.IterExt.html#method.sliding_dft).
+    pub fn new(iter: I, bin: usize, window_size: usize) -> SlidingDft<I> {
+        SlidingDft {
+            iter,
+            bin,
+            window: Ring::new(window_size),
+            twiddle: Complex::from_phase(2.0 * PI * bin as f64 / window_size as f64),
+            value: Complex::default(),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for SlidingDft<I> where I::Item: WindowNum {
+    type Item = Complex;
+
+    fn next(&mut self) -> Option<Complex> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for x in &mut self.iter {
+            let x = x.to_f64();
+            let position = self.window.len();
+
+            match self.window.push(x) {
+                // the window is full, slide it in O(1)
+                Some(old) => self.value = (self.value + Complex::new(x - old, 0.0)) * self.twiddle,
+                // still filling up, add the contribution of x directly
+                None => {
+                    let n = self.window.capacity() as f64;
+                    let phi = -2.0 * PI * self.bin as f64 * position as f64 / n;
+                    self.value = self.value + Complex::from_phase(phi) * Complex::new(x, 0.0);
+                },
+            }
+
+            if self.window.is_full() {
+                return Some(self.value);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,129 @@
+use ring::Ring;
+
+/// Iterator yielding the Hamming distance between every window and a reference.
+///
+/// See [IterExt::hamming_distance](trait.IterExt.html#method.hamming_distance) for more information.
+pub struct HammingDistance<'r, I: Iterator> {
+    iter: I,
+    reference: &'r [I::Item],
+    window: Ring<I::Item>,
+}
+
+impl<'r, I: Iterator> HammingDistance<'r, I> where I::Item: PartialEq {
+    /// This creates a new HammingDistance iterator. Usually you should be using
+    /// [IterExt::hamming_distance](trait.IterExt.html#method.hamming_distance).
+    pub fn new(iter: I, reference: &'r [I::Item]) -> HammingDistance<'r, I> {
+        HammingDistance {
+            iter,
+            reference,
+            window: Ring::new(reference.len()),
+        }
+    }
+}
+
+impl<'r, I: Iterator> Iterator for HammingDistance<'r, I> where I::Item: PartialEq {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.reference.is_empty() {
+            return None;
+        }
+
+        for elt in &mut self.iter {
+            self.window.push(elt);
+            if self.window.is_full() {
+                let distance = self.window.iter().zip(self.reference).filter(|&(x, r)| x != r).count();
+                return Some(distance);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// Iterator yielding the edit distance between every window and a reference, if it is
+/// within a band.
+///
+/// See [IterExt::edit_distance](trait.IterExt.html#method.edit_distance) for more information.
+pub struct EditDistance<'r, I: Iterator> {
+    iter: I,
+    reference: &'r [I::Item],
+    band: usize,
+    window: Ring<I::Item>,
+    // the previous and the current row of the dynamic program, reused for every window
+    previous: Vec<usize>,
+    current: Vec<usize>,
+}
+
+impl<'r, I: Iterator> EditDistance<'r, I> where I::Item: PartialEq {
+    /// This creates a new EditDistance iterator. Usually you should be using
+    /// [IterExt::edit_distance](trait.IterExt.html#method.edit_distance).
+    pub fn new(iter: I, reference: &'r [I::Item], band: usize) -> EditDistance<'r, I> {
+        let len = reference.len();
+        EditDistance {
+            iter,
+            reference,
+            band,
+            window: Ring::new(len),
+            previous: vec![0; len + 1],
+            current: vec![0; len + 1],
+        }
+    }
+
+    // Levenshtein distance between the window and the reference, only considering
+    // alignments which stay within `band` of the diagonal
+    fn distance(&mut self) -> Option<usize> {
+        let len = self.reference.len();
+        let band = self.band;
+        // larger than any distance within the band
+        let out = band + 1;
+
+        for (j, cell) in self.previous.iter_mut().enumerate() {
+            *cell = if j <= band { j } else { out };
+        }
+
+        for (i, x) in self.window.iter().enumerate().map(|(i, x)| (i + 1, x)) {
+            let lo = i.saturating_sub(band);
+            let hi = len.min(i + band);
+            self.current[0] = if i <= band { i } else { out };
+            for j in 1..=len {
+                self.current[j] = if j < lo || j > hi {
+                    out
+                } else {
+                    let substitute = self.previous[j - 1] + (x != &self.reference[j - 1]) as usize;
+                    let delete = self.previous[j] + 1;
+                    let insert = self.current[j - 1] + 1;
+                    substitute.min(delete).min(insert).min(out)
+                };
+            }
+            ::std::mem::swap(&mut self.previous, &mut self.current);
+        }
+
+        Some(self.previous[len]).filter(|&distance| distance <= band)
+    }
+}
+
+impl<'r, I: Iterator> Iterator for EditDistance<'r, I> where I::Item: PartialEq {
+    type Item = Option<usize>;
+
+    fn next(&mut self) -> Option<Option<usize>> {
+        if self.reference.is_empty() {
+            return None;
+        }
+
+        for elt in &mut self.iter {
+            self.window.push(elt);
+            if self.window.is_full() {
+                return Some(self.distance());
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
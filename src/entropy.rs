@@ -0,0 +1,89 @@
+use ring::Ring;
+
+/// Iterator yielding the Shannon entropy of every window of a byte stream.
+///
+/// See [IterExt::rolling_entropy](trait.IterExt.html#method.rolling_entropy) for more information.
+pub struct RollingEntropy<I> {
+    iter: I,
+    window: Ring<u8>,
+    histogram: Histogram,
+}
+
+// byte counts of the window, maintaining the sum the entropy is computed from
+struct Histogram {
+    counts: [usize; 256],
+    // `c * log2(c)` for every count `c` up to the window size
+    terms: Vec<f64>,
+    // the sum of the terms of all counts, updated with every element
+    sum: f64,
+    // elements pushed since `sum` was last recomputed from the counts
+    since_sync: usize,
+}
+
+impl<I: Iterator<Item = u8>> RollingEntropy<I> {
+    /// This creates a new RollingEntropy iterator. Usually you should be using
+    /// [IterExt::rolling_entropy](trait.IterExt.html#method.rolling_entropy).
+    pub fn new(iter: I, window_size: usize) -> RollingEntropy<I> {
+        RollingEntropy {
+            iter,
+            window: Ring::new(window_size),
+            histogram: Histogram {
+                counts: [0; 256],
+                terms: (0..=window_size).map(|c| if c == 0 { 0.0 } else { c as f64 * (c as f64).log2() }).collect(),
+                sum: 0.0,
+                since_sync: 0,
+            },
+        }
+    }
+}
+
+impl Histogram {
+    fn count(&mut self, byte: u8, increment: bool) {
+        let count = &mut self.counts[byte as usize];
+        let before = self.terms[*count];
+        if increment { *count += 1 } else { *count -= 1 }
+        self.sum += self.terms[*count] - before;
+    }
+
+    fn push(&mut self, byte: u8, evicted: Option<u8>, window_size: usize) {
+        // evict first, so that no count exceeds the window size
+        if let Some(old) = evicted {
+            self.count(old, false);
+        }
+        self.count(byte, true);
+
+        // recompute the sum once per window to bound the rounding error of the updates
+        self.since_sync += 1;
+        if self.since_sync == window_size {
+            self.sum = self.counts.iter().map(|&c| self.terms[c]).sum();
+            self.since_sync = 0;
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for RollingEntropy<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let window_size = self.window.capacity();
+        if window_size == 0 {
+            return None;
+        }
+
+        for byte in &mut self.iter {
+            let evicted = self.window.push(byte);
+            self.histogram.push(byte, evicted, window_size);
+
+            if self.window.is_full() {
+                let n = window_size as f64;
+                // H = -sum(c / n * log2(c / n)) = log2(n) - sum(c * log2(c)) / n
+                return Some((n.log2() - self.histogram.sum / n).max(0.0));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use ring::Ring;
+
+/// An exact fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numer: i128,
+    denom: i128,
+}
+
+impl Ratio {
+    /// Creates the fraction `numer / denom` in lowest terms.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `denom` is zero.
+    pub fn new(numer: i128, denom: i128) -> Ratio {
+        assert!(denom != 0, "denominator of a Ratio must not be zero");
+
+        let mut gcd = gcd(numer.unsigned_abs(), denom.unsigned_abs()) as i128;
+        if denom < 0 {
+            gcd = -gcd;
+        }
+        Ratio { numer: numer / gcd, denom: denom / gcd }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    /// The nearest `f64`, only intended for display purposes.
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The exact sums of a window of integers, from which its mean and variance are derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExactStats {
+    pub count: usize,
+    pub sum: i128,
+    pub sum_sq: i128,
+}
+
+impl ExactStats {
+    pub fn mean(&self) -> Ratio {
+        Ratio::new(self.sum, self.count as i128)
+    }
+
+    /// The population variance, dividing by `count`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if an intermediate result overflows `i128`.
+    pub fn variance(&self) -> Ratio {
+        let n = self.count as i128;
+        Ratio::new(self.scaled_variance(), checked(n.checked_mul(n)))
+    }
+
+    /// The sample variance, dividing by `count - 1`. It is undefined for a single element.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if an intermediate result overflows `i128`.
+    pub fn sample_variance(&self) -> Option<Ratio> {
+        let n = self.count as i128;
+        if n < 2 {
+            return None;
+        }
+        Some(Ratio::new(self.scaled_variance(), checked(n.checked_mul(n - 1))))
+    }
+
+    // n * sum_sq - sum * sum, which is n * n times the population variance
+    fn scaled_variance(&self) -> i128 {
+        let n = self.count as i128;
+        let sum_sq = checked(n.checked_mul(self.sum_sq));
+        checked(sum_sq.checked_sub(checked(self.sum.checked_mul(self.sum))))
+    }
+}
+
+// the exact results can't be rounded, so an overflow is an error
+fn checked(x: Option<i128>) -> i128 {
+    x.expect("exact statistics overflowed i128")
+}
+
+/// Iterator yielding the `ExactStats` of the trailing window.
+///
+/// See [IterExt::rolling_exact_stats](trait.IterExt.html#method.rolling_exact_stats) for more information.
+pub struct RollingExactStats<I> {
+    iter: I,
+    window: Ring<i128>,
+    sum: i128,
+    sum_sq: i128,
+}
+
+impl<I: Iterator> RollingExactStats<I> where I::Item: Into<i128> {
+    /// This creates a new RollingExactStats. Usually you should be using
+    /// [IterExt::rolling_exact_stats](trait.IterExt.html#method.rolling_exact_stats).
+    pub fn new(iter: I, window_size: usize) -> RollingExactStats<I> {
+        RollingExactStats {
+            iter,
+            window: Ring::new(window_size),
+            sum: 0,
+            sum_sq: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingExactStats<I> where I::Item: Into<i128> {
+    type Item = ExactStats;
+
+    fn next(&mut self) -> Option<ExactStats> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for x in &mut self.iter {
+            let x = x.into();
+            self.sum = checked(self.sum.checked_add(x));
+            self.sum_sq = checked(self.sum_sq.checked_add(checked(x.checked_mul(x))));
+
+            if let Some(old) = self.window.push(x) {
+                // old * old was added before, so it doesn't overflow
+                self.sum -= old;
+                self.sum_sq -= old * old;
+            }
+
+            if self.window.is_full() {
+                return Some(ExactStats {
+                    count: self.window.capacity(),
+                    sum: self.sum,
+                    sum_sq: self.sum_sq,
+                });
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::iter::Fuse;
+
+/// The label of an element yielded by [LabelExtrema](struct.LabelExtrema.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Extremum {
+    /// The element is smaller than all of its neighbours.
+    Min,
+    /// The element is larger than all of its neighbours.
+    Max,
+    /// The element ties with or lies between its neighbours, or has none.
+    Neither,
+}
+
+/// Iterator labeling every element as a local minimum, maximum or neither.
+///
+/// See [IterExt::label_extrema](trait.IterExt.html#method.label_extrema) for more information.
+pub struct LabelExtrema<I: Iterator> {
+    iter: Fuse<I>,
+    radius: usize,
+    // the elements from `radius` before up to `radius` after the next element to label
+    buffer: VecDeque<I::Item>,
+    // the position of the next element to label in `buffer`
+    center: usize,
+}
+
+impl<I: Iterator> LabelExtrema<I> where I::Item: PartialOrd {
+    /// This creates a new LabelExtrema iterator. Usually you should be using
+    /// [IterExt::label_extrema](trait.IterExt.html#method.label_extrema).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `radius` is zero.
+    pub fn new(iter: I, radius: usize) -> LabelExtrema<I> {
+        assert!(radius > 0, "radius must be positive");
+        LabelExtrema {
+            iter: iter.fuse(),
+            radius,
+            buffer: VecDeque::with_capacity(2 * radius + 1),
+            center: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for LabelExtrema<I> where I::Item: PartialOrd {
+    type Item = Extremum;
+
+    fn next(&mut self) -> Option<Extremum> {
+        // pull the right neighbours, there are less at the end of the input
+        while self.buffer.len() <= self.center + self.radius {
+            match self.iter.next() {
+                Some(elt) => self.buffer.push_back(elt),
+                None => break,
+            }
+        }
+
+        let center = self.buffer.get(self.center)?;
+        let mut neighbours = self.buffer.iter().enumerate()
+            .filter(|&(i, _)| i != self.center)
+            .map(|(_, x)| x)
+            .peekable();
+
+        let label = if neighbours.peek().is_none() {
+            Extremum::Neither
+        } else {
+            let (mut min, mut max) = (true, true);
+            for x in neighbours {
+                min &= center < x;
+                max &= center > x;
+            }
+            match (min, max) {
+                (true, _) => Extremum::Min,
+                (_, true) => Extremum::Max,
+                _ => Extremum::Neither,
+            }
+        };
+
+        if self.center == self.radius {
+            self.buffer.pop_front();
+        } else {
+            self.center += 1;
+        }
+        Some(label)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffer.len() - self.center.min(self.buffer.len());
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(buffered), upper.and_then(|upper| upper.checked_add(buffered)))
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread::{self, JoinHandle};
+
+use crossbeam_channel::{bounded, Receiver, Sender};
+
+use sliding_windows::{Adaptor, AsWindowStorage, Storage};
+
+// the number of windows a single worker may have queued, processing or finished but not yet
+// collected
+const WORKER_BOUND: usize = 2;
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: Clone + Send + 'static {
+    /// Processes owned copies of the windows with `f` on `n_workers` threads and yields the
+    /// results in window order.
+    ///
+    /// The windows are handed to the workers round-robin over bounded channels, so at most a
+    /// few windows per worker are copied ahead of the consumer. The wrapped iterator is still
+    /// driven by the calling thread, it doesn't have to be `Send`.
+    ///
+    /// Dropping the returned `Fanout` waits for the workers to finish their current window.
+    ///
+    /// Only available with the `crossbeam` feature.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `n_workers` is zero. `Fanout::next` panics if a worker panicked.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u64> = Storage::new(3);
+    /// let sums: Vec<u64> = (1..7u64)
+    ///     .sliding_windows(&mut storage)
+    ///     .spawn_fanout(4, |window| window.iter().sum())
+    ///     .collect();
+    ///
+    /// assert_eq!(sums, [6, 9, 12, 15]);
+    /// ```
+    pub fn spawn_fanout<F, R>(self, n_workers: usize, f: F) -> Fanout<'a, I, R, S>
+        where F: Fn(Vec<I::Item>) -> R + Send + Clone + 'static, R: Send + 'static
+    {
+        assert!(n_workers > 0, "number of workers must be positive");
+
+        let mut windows = Vec::with_capacity(n_workers);
+        let mut results = Vec::with_capacity(n_workers);
+        let mut workers = Vec::with_capacity(n_workers);
+        for _ in 0..n_workers {
+            let (window_tx, window_rx) = bounded::<Vec<I::Item>>(WORKER_BOUND);
+            let (result_tx, result_rx) = bounded(WORKER_BOUND);
+            let f = f.clone();
+            workers.push(thread::spawn(move || {
+                for window in window_rx {
+                    if result_tx.send(f(window)).is_err() {
+                        break;
+                    }
+                }
+            }));
+            windows.push(window_tx);
+            results.push(result_rx);
+        }
+
+        Fanout {
+            inner: self,
+            done: false,
+            windows,
+            results,
+            workers,
+            sent: 0,
+            received: 0,
+        }
+    }
+
+    /// Maps owned copies of the windows with `f` on a pool of worker threads, one per
+    /// available CPU, and yields the results in window order.
+    ///
+    /// Unlike [spawn_fanout](#method.spawn_fanout), idle workers take the next window from a
+    /// shared queue, so a slow window doesn't hold up the windows queued behind it. Results
+    /// finishing early are buffered until all previous results were yielded, the number of
+    /// windows in flight is bounded by a few per worker.
+    ///
+    /// Only available with the `crossbeam` feature.
+    ///
+    /// # Panics:
+    ///
+    /// `ParMapWindows::next` resumes the panic if `f` panicked on one of the windows.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u64> = Storage::new(3);
+    /// let maxima: Vec<u64> = [3, 1, 4, 1, 5, 9, 2, 6].iter().cloned()
+    ///     .sliding_windows(&mut storage)
+    ///     .par_map_windows_ordered(|window| window.into_iter().max().unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(maxima, [4, 4, 5, 9, 9, 9]);
+    /// ```
+    pub fn par_map_windows_ordered<F, R>(self, f: F) -> ParMapWindows<'a, I, R, S>
+        where F: Fn(Vec<I::Item>) -> R + Send + Clone + 'static, R: Send + 'static
+    {
+        let n_workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let (window_tx, window_rx) = bounded::<(usize, Vec<I::Item>)>(n_workers * WORKER_BOUND);
+        let (result_tx, result_rx) = bounded(n_workers * WORKER_BOUND);
+
+        let workers = (0..n_workers).map(|_| {
+            let (window_rx, result_tx, f) = (window_rx.clone(), result_tx.clone(), f.clone());
+            thread::spawn(move || {
+                for (index, window) in window_rx {
+                    // a panic is caught and handed to the consumer, the worker keeps going
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| f(window)));
+                    if result_tx.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            })
+        }).collect();
+
+        ParMapWindows {
+            inner: self,
+            done: false,
+            windows: Some(window_tx),
+            results: result_rx,
+            workers,
+            pending: VecDeque::new(),
+            sent: 0,
+            received: 0,
+        }
+    }
+}
+
+/// Results of processing windows on worker threads, see
+/// [Adaptor::spawn_fanout](struct.Adaptor.html#method.spawn_fanout).
+///
+/// Only available with the `crossbeam` feature.
+pub struct Fanout<'a, I: Iterator, R, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a {
+    inner: Adaptor<'a, I, S>,
+    // whether `inner` is exhausted
+    done: bool,
+    // window `i` is processed by worker `i % n_workers`, whose results arrive in order
+    windows: Vec<Sender<Vec<I::Item>>>,
+    results: Vec<Receiver<R>>,
+    workers: Vec<JoinHandle<()>>,
+    sent: usize,
+    received: usize,
+}
+
+impl<'a, I: Iterator, R, S: AsWindowStorage<I::Item>> Iterator for Fanout<'a, I, R, S> where I::Item: Clone {
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        let n_workers = self.workers.len();
+
+        // keep every worker busy without ever blocking on a full channel
+        while !self.done && self.sent - self.received < n_workers * WORKER_BOUND {
+            match self.inner.next() {
+                Some(window) => {
+                    let window = window.iter().cloned().collect();
+                    if self.windows[self.sent % n_workers].send(window).is_err() {
+                        panic!("fanout worker {} panicked", self.sent % n_workers);
+                    }
+                    self.sent += 1;
+                }
+                None => self.done = true,
+            }
+        }
+
+        if self.received == self.sent {
+            return None;
+        }
+        let worker = self.received % n_workers;
+        match self.results[worker].recv() {
+            Ok(result) => {
+                self.received += 1;
+                Some(result)
+            }
+            Err(_) => panic!("fanout worker {} panicked", worker),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.sent - self.received;
+        if self.done {
+            return (pending, Some(pending));
+        }
+        let (lower, upper) = self.inner.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<'a, I: Iterator, R, S: 'a> Drop for Fanout<'a, I, R, S> where <I as Iterator>::Item: 'a {
+    fn drop(&mut self) {
+        // closing the window channels stops the workers after their queued windows
+        self.windows.clear();
+        for worker in self.workers.drain(..) {
+            // a panic of a worker was reported by `next` already
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Results of mapping windows on a pool of worker threads, in window order, see
+/// [Adaptor::par_map_windows_ordered](struct.Adaptor.html#method.par_map_windows_ordered).
+///
+/// Only available with the `crossbeam` feature.
+pub struct ParMapWindows<'a, I: Iterator, R, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a {
+    inner: Adaptor<'a, I, S>,
+    // whether `inner` is exhausted
+    done: bool,
+    windows: Option<Sender<(usize, Vec<I::Item>)>>,
+    results: Receiver<(usize, thread::Result<R>)>,
+    workers: Vec<JoinHandle<()>>,
+    // the results of the windows from `received` on, `None` while still being processed
+    pending: VecDeque<Option<thread::Result<R>>>,
+    sent: usize,
+    received: usize,
+}
+
+impl<'a, I: Iterator, R, S: AsWindowStorage<I::Item>> Iterator for ParMapWindows<'a, I, R, S> where I::Item: Clone {
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        let limit = self.workers.len() * WORKER_BOUND;
+        let windows = self.windows.as_ref().expect("window channel is open until drop");
+
+        // the channels hold `limit` elements, so sending and receiving never blocks forever
+        while !self.done && self.sent - self.received < limit {
+            match self.inner.next() {
+                Some(window) => {
+                    let window = window.iter().cloned().collect();
+                    windows.send((self.sent, window)).expect("a worker is alive while results are pending");
+                    self.pending.push_back(None);
+                    self.sent += 1;
+                }
+                None => self.done = true,
+            }
+        }
+
+        while self.pending.front().is_some_and(Option::is_none) {
+            let (index, result) = self.results.recv().expect("a worker is alive while results are pending");
+            self.pending[index - self.received] = Some(result);
+        }
+
+        let result = self.pending.pop_front()??;
+        self.received += 1;
+        match result {
+            Ok(result) => Some(result),
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.sent - self.received;
+        if self.done {
+            return (pending, Some(pending));
+        }
+        let (lower, upper) = self.inner.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<'a, I: Iterator, R, S: 'a> Drop for ParMapWindows<'a, I, R, S> where <I as Iterator>::Item: 'a {
+    fn drop(&mut self) {
+        // closing the window channel stops the workers after the queued windows
+        self.windows = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use sliding_windows::{AsWindowStorage, Storage, Window};
+
+/// A `Storage` whose window size `N` is part of the type.
+///
+/// The window size is a constant for the `Adaptor`, which lets the compiler specialize the
+/// window handling for small windows. Use it with
+/// [IterExt::sliding_windows](trait.IterExt.html#method.sliding_windows).
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::{FixedStorage, IterExt};
+///
+/// let mut storage: FixedStorage<u32, 3> = FixedStorage::new();
+/// let sums: Vec<u32> = (0..5).sliding_windows(&mut storage).map(|w| w.iter().sum()).collect();
+///
+/// assert_eq!(sums, [3, 6, 9]);
+/// ```
+pub struct FixedStorage<T, const N: usize> {
+    inner: Storage<T>,
+}
+
+impl<T, const N: usize> FixedStorage<T, N> {
+    /// Create a new `FixedStorage`, allocating room for `N` elements.
+    pub fn new() -> FixedStorage<T, N> {
+        FixedStorage { inner: Storage::new(N) }
+    }
+
+    /// Consumes the `FixedStorage` and returns the buffered elements in logical order,
+    /// see [Storage::into_vec](struct.Storage.html#method.into_vec).
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner.into_vec()
+    }
+
+    /// See [Storage::is_poisoned](struct.Storage.html#method.is_poisoned).
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// See [Storage::clear_poison](struct.Storage.html#method.clear_poison).
+    pub fn clear_poison(&mut self) {
+        self.inner.clear_poison()
+    }
+}
+
+impl<T, const N: usize> Default for FixedStorage<T, N> {
+    fn default() -> FixedStorage<T, N> {
+        FixedStorage::new()
+    }
+}
+
+impl<T, const N: usize> AsWindowStorage<T> for FixedStorage<T, N> {
+    fn window_size(&self) -> usize {
+        N
+    }
+
+    fn clear(&self) {
+        AsWindowStorage::clear(&self.inner)
+    }
+
+    fn push(&self, elt: T) -> bool {
+        self.inner.push(elt)
+    }
+
+    fn new_window(&self) -> Window<'_, T> {
+        self.inner.new_window()
+    }
+
+    fn poison(&self) {
+        self.inner.poison()
+    }
+
+    fn assert_not_poisoned(&self) {
+        self.inner.assert_not_poisoned()
+    }
+
+    fn assert_no_window(&self) {
+        AsWindowStorage::assert_no_window(&self.inner)
+    }
+
+    fn new_suffix_window(&self, fill: usize) -> Option<Window<'_, T>> {
+        self.inner.new_suffix_window(fill)
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use sliding_windows::{Storage, Window};
+
+type EvictFn<'f, K, T> = Box<dyn FnMut(K, Vec<T>) + 'f>;
+
+struct Entry<T> {
+    storage: Storage<T>,
+    // the tick of the last push, used to find the least recently used key
+    last_used: u64,
+}
+
+/// Separate sliding windows for every key of an interleaved stream.
+///
+/// Every key gets its own [Storage](struct.Storage.html), which is created on the first
+/// element of the key. To bound the memory of streams with many keys, the number of tracked
+/// keys can be capped, evicting the least recently used key.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::KeyedWindows;
+///
+/// let mut windows = KeyedWindows::new(2).with_max_keys(100);
+/// let events = [("a", 1), ("b", 10), ("a", 2), ("b", 20), ("a", 3)];
+/// let mut output = Vec::new();
+///
+/// for &(key, value) in &events {
+///     if let Some(window) = windows.push(key, value) {
+///         output.push((key, window.iter().sum::<u32>()));
+///     }
+/// }
+///
+/// assert_eq!(output, [("a", 3), ("b", 30), ("a", 5)]);
+/// ```
+pub struct KeyedWindows<'f, K, T> {
+    window_size: usize,
+    entries: HashMap<K, Entry<T>>,
+    // keys ordered by their last use
+    lru: BTreeMap<u64, K>,
+    clock: u64,
+    max_keys: Option<usize>,
+    on_evict: Option<EvictFn<'f, K, T>>,
+}
+
+impl<'f, K: Hash + Eq + Clone, T> KeyedWindows<'f, K, T> {
+    /// Create an empty `KeyedWindows` yielding windows of `window_size` elements.
+    pub fn new(window_size: usize) -> KeyedWindows<'f, K, T> {
+        KeyedWindows {
+            window_size,
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+            max_keys: None,
+            on_evict: None,
+        }
+    }
+
+    /// Track at most `max_keys` keys. Pushing an element for a new key while the limit is
+    /// reached evicts the least recently used key, dropping its buffered elements.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `max_keys` is zero.
+    pub fn with_max_keys(mut self, max_keys: usize) -> KeyedWindows<'f, K, T> {
+        assert!(max_keys > 0, "max_keys must be positive");
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    /// Call `on_evict` with the key and the buffered elements (oldest first) of every key
+    /// evicted because of the [key limit](#method.with_max_keys).
+    pub fn on_evict<F>(mut self, on_evict: F) -> KeyedWindows<'f, K, T> where F: FnMut(K, Vec<T>) + 'f {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    /// The number of tracked keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Stop tracking `key`, returning its buffered elements (oldest first).
+    /// The eviction callback is not called.
+    pub fn remove(&mut self, key: &K) -> Option<Vec<T>> {
+        let entry = self.entries.remove(key)?;
+        self.lru.remove(&entry.last_used);
+        Some(entry.storage.into_vec())
+    }
+
+    /// Push `elt` onto the window of `key`, returning the window if it is full.
+    ///
+    /// As with [Adaptor](struct.Adaptor.html), the returned window has to be dropped before
+    /// the next call.
+    pub fn push(&mut self, key: K, elt: T) -> Option<Window<'_, T>> {
+        self.clock += 1;
+        let clock = self.clock;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            let key = self.lru.remove(&entry.last_used).expect("tracked keys are in the lru map");
+            self.lru.insert(clock, key);
+            entry.last_used = clock;
+        } else {
+            if self.max_keys == Some(self.entries.len()) {
+                self.evict_lru();
+            }
+            self.lru.insert(clock, key.clone());
+            self.entries.insert(key.clone(), Entry {
+                storage: Storage::new(self.window_size),
+                last_used: clock,
+            });
+        }
+
+        let storage = &self.entries[&key].storage;
+        if self.window_size > 0 && storage.push(elt) {
+            Some(storage.new_window())
+        } else {
+            None
+        }
+    }
+
+    fn evict_lru(&mut self) {
+        let oldest = match self.lru.keys().next() {
+            Some(&oldest) => oldest,
+            None => return,
+        };
+        let key = self.lru.remove(&oldest).expect("key was just found");
+        let entry = self.entries.remove(&key).expect("tracked keys are in the entry map");
+
+        if let Some(ref mut on_evict) = self.on_evict {
+            on_evict(key, entry.storage.into_vec());
+        }
+    }
+}
@@ -30,7 +30,7 @@
 //! assert_eq!(output, expected);
 //! ```
 //!
-//! It's also possible to reuse an allocation for `Storage` via the `Into` trait.
+//! It's also possible to reuse an allocation for `Storage` via `Storage::from_vec()` and `Storage::into_vec()`.
 //!
 //! # Example:
 //!
@@ -42,14 +42,15 @@
 //! let mut storage: Storage<u32> = Storage::from_vec(previous_alloca, 3);
 //! let expected: &[&[u32]] = &[&[0,1,2], &[1,2,3], &[2,3,4]];
 //!
-//! // extra scope so that windowed_iter doesn't outlive storage.into() call
+//! // extra scope so that windowed_iter doesn't outlive storage.into_vec() call
 //! {
 //!     let windowed_iter = (0..5).sliding_windows(&mut storage);
 //!     let output: Vec<Vec<u32>> = windowed_iter.map(|x| x.iter().map(|&x| x).collect()).collect();
 //!     assert_eq!(output, expected);
 //! }
 //!
-//! let reusing_alloca: Vec<u32> = storage.into();
+//! let reusing_alloca: Vec<u32> = storage.into_vec();
+//! assert_eq!(reusing_alloca, [2, 3, 4]);
 //! // keep using allocation of storage
 //!
 //! ```
@@ -78,6 +79,50 @@
 //! }
 //! ```
 //!
+//! ### Unwind safety:
+//!
+//! A panic unwinding through `next()` poisons the `Storage`, like a `Mutex`, and the adaptor
+//! as well as new adaptors refuse to use it until
+//! [Storage::clear_poison](struct.Storage.html#method.clear_poison) is called. Poisoning doesn't
+//! cover panics while a live `Window` is mutated through `iter_mut`, so `Storage<T>` is
+//! `UnwindSafe` and `RefUnwindSafe` only if `T` is. An `Adaptor` may hold arbitrary closures,
+//! e.g. from [with_decay](struct.Adaptor.html#method.with_decay), so it isn't `UnwindSafe`,
+//! but thanks to the poisoning it can be moved into `catch_unwind` with `AssertUnwindSafe`:
+//!
+//! ```
+//! use std::panic::{self, AssertUnwindSafe};
+//! use sliding_windows::{IterExt, Storage};
+//!
+//! let mut storage: Storage<u32> = Storage::new(3);
+//! let windows = (0..5).sliding_windows(&mut storage);
+//! let sums = panic::catch_unwind(AssertUnwindSafe(move || windows.map(|w| w.iter().sum::<u32>()).collect::<Vec<_>>()));
+//! assert_eq!(sums.unwrap(), [3, 6, 9]);
+//! ```
+//!
+//! A `Window` borrows its elements mutably, so like `&mut [T]` it isn't `UnwindSafe`:
+//!
+//! ```compile_fail
+//! use std::panic;
+//! use sliding_windows::{IterExt, Storage};
+//!
+//! let mut storage: Storage<u32> = Storage::new(3);
+//! let mut windows = (0..5).sliding_windows(&mut storage);
+//! let window = windows.next().unwrap();
+//! panic::catch_unwind(move || window.iter().sum::<u32>()).unwrap();
+//! ```
+//!
+//! Shared references to a `Window` are, if the elements are `RefUnwindSafe`:
+//!
+//! ```
+//! use std::panic;
+//! use sliding_windows::{IterExt, Storage};
+//!
+//! let mut storage: Storage<u32> = Storage::new(3);
+//! let mut windows = (0..5).sliding_windows(&mut storage);
+//! let window = windows.next().unwrap();
+//! assert_eq!(panic::catch_unwind(|| window.iter().sum::<u32>()).unwrap(), 3);
+//! ```
+//!
 //! # Mutable Window:
 //!
 //! There is an implementation of an Iterator over `&'a mut T` for `Window<'a, T>`. It can be obtained
@@ -85,29 +130,1172 @@
 //!
 //! However be aware that changes made to the items in the Window are persistent through calls to `next()`.
 
-#![cfg_attr(test, feature(test))]
-
-#[cfg(test)]
-extern crate test;
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "arrow")]
+extern crate arrow_schema;
+#[cfg(any(feature = "serde", feature = "csv"))]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "itertools")]
+extern crate smallvec;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "crossbeam")]
+extern crate crossbeam_channel;
 
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
-mod benches;
+use std::hash::Hash;
+use std::iter::Map;
+use std::ops::Sub;
 
 mod sliding_windows;
+mod num;
+mod ring;
+mod rolling;
+mod debounce;
+mod majority;
+mod decimate;
+mod lttb;
+mod resample;
+mod dft;
+mod exact;
+mod keyed;
+mod tumbling;
+mod summary;
+mod nulls;
+mod paired;
+mod padded;
+mod timebucket;
+mod bus;
+mod fixed;
+mod array;
+mod bits;
+mod bytes;
+mod chars;
+mod distance;
+mod entropy;
+mod checksum;
+mod runs;
+mod extrema;
+mod topk;
+mod recent;
+mod record;
+mod search;
+mod skew;
+mod view;
+#[cfg(feature = "bloom")]
+mod bloom;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "itertools")]
+mod owned;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "crossbeam")]
+mod fanout;
+#[cfg(any(feature = "serde", feature = "csv"))]
+mod write;
+
+pub mod detect;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+// internals exposed to the benchmarks in benches/, not part of the public API
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub mod internals {
+    pub use ring::Ring;
+}
 
 pub use sliding_windows::{
-    Storage, Adaptor, Window, WindowIter, WindowIterMut};
+    Storage, AsWindowStorage, Adaptor, ClonedWindows, StorageInUse, OnShort, sliding_windows_chain, windows_len,
+    Window, WindowIter, WindowIterMut, DisplayWith};
+pub use num::WindowNum;
+pub use rolling::{RollingCov, RollingCorr, RollingTrend, Trend, RollingSum, RollingMean, Summation,
+                  RollingSumSkipNan, RollingMeanSkipNan};
+pub use debounce::{Debounce, Hysteresis};
+pub use majority::{RollingMajority, TiePolicy};
+pub use decimate::Decimate;
+pub use lttb::Lttb;
+pub use resample::{Interpolation, Resample};
+pub use dft::{Complex, SlidingDft};
+pub use exact::{ExactStats, Ratio, RollingExactStats};
+pub use keyed::KeyedWindows;
+pub use tumbling::TumblingBy;
+pub use summary::{SummarizedWindow, WindowStats};
+pub use nulls::{NullPolicy, FillNulls, ForwardFill, BackwardFill};
+pub use paired::{Paired, Originals, Derived};
+pub use padded::Padded;
+pub use timebucket::{EmptyBucket, TimeBuckets};
+pub use bus::WindowBus;
+pub use fixed::FixedStorage;
+pub use array::{ArrayStorage, ArrayWindows};
+pub use bits::{BitMask, Bits, BitWindows, FindSync};
+pub use bytes::{byte_windows, ByteWindows};
+pub use chars::CharWindows;
+pub use distance::{HammingDistance, EditDistance};
+pub use entropy::RollingEntropy;
+pub use checksum::{RollingAdler32, RollingCrc32};
+pub use runs::{Run, Runs};
+pub use extrema::{Extremum, LabelExtrema};
+pub use topk::RollingTopK;
+pub use recent::{RecentSet, DedupWithin, RecentSample};
+pub use record::{Trace, Recorder, Replay};
+pub use search::FindSubsequence;
+pub use skew::{Skew, TimestampSkew};
+pub use view::WindowedView;
+#[cfg(feature = "bloom")]
+pub use bloom::{ApproxRecentSet, ApproxDedupWithin};
+#[cfg(any(feature = "serde", feature = "csv"))]
+pub use write::WindowFormat;
+#[cfg(feature = "itertools")]
+pub use owned::OwnedWindows;
+#[cfg(feature = "rand")]
+pub use random::WindowIterShuffled;
+#[cfg(feature = "crossbeam")]
+pub use fanout::{Fanout, ParMapWindows};
+use detect::{Detector, Detections};
+use runs::EqFn;
 
 pub trait IterExt: Iterator {
-    fn sliding_windows(self, storage: &mut Storage<Self::Item>)
-        -> Adaptor<Self>
+    /// Yields sliding windows into the elements of `self`, see [sliding_windows](index.html).
+    ///
+    /// The adaptor pulls no more elements than needed for the next window, so it can be used
+    /// on `iter.by_ref()` to window a prefix and continue with the original iterator right
+    /// after the last element of the last window:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let mut iter = 0..6;
+    ///
+    /// for window in iter.by_ref().sliding_windows(&mut storage).take(2) {
+    ///     assert_eq!(window.iter().count(), 2);
+    /// }
+    ///
+    /// assert_eq!(iter.collect::<Vec<_>>(), [3, 4, 5]);
+    /// ```
+    ///
+    /// Any [AsWindowStorage](trait.AsWindowStorage.html) can buffer the elements, e.g. a
+    /// [FixedStorage](struct.FixedStorage.html) with the window size fixed at compile time or
+    /// an [ArrayStorage](struct.ArrayStorage.html) which never allocates.
+    fn sliding_windows<S: AsWindowStorage<Self::Item>>(self, storage: &mut S)
+        -> Adaptor<'_, Self, S>
         where Self: Sized
     {
         Adaptor::new(self, storage)
     }
+
+    /// Random access to the full windows of `storage.window_size()` elements over a source
+    /// which is cheap to clone and knows its length, e.g. to binary search over windows.
+    /// Every access clones the source and skips to the window.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut view = (0..1000).windowed_view(&mut storage);
+    ///
+    /// assert_eq!(view.len(), 998);
+    /// assert_eq!(view.window_at(500).unwrap(), &[500, 501, 502][..]);
+    /// assert!(view.window_at(998).is_none());
+    /// ```
+    fn windowed_view(self, storage: &mut Storage<Self::Item>) -> WindowedView<'_, Self>
+        where Self: Sized + ExactSizeIterator + Clone
+    {
+        WindowedView::new(self, storage)
+    }
+
+    /// Yields sliding windows of `N` elements as arrays, which can be destructured with a
+    /// pattern and outlive the next window, so the runtime check of `Window`s is not needed.
+    /// The elements have to be `Copy`, e.g. references from `slice.iter()` yield `[&T; N]`
+    /// and numbers `[T; N]`, so no window clones its elements.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let peaks: Vec<u32> = [1, 3, 2, 5, 4].iter().cloned().array_windows()
+    ///     .filter_map(|[a, b, c]| if a < b && b > c { Some(b) } else { None })
+    ///     .collect();
+    ///
+    /// assert_eq!(peaks, [3, 5]);
+    ///
+    /// let words = ["to", "be", "or", "not"].map(String::from);
+    /// let pairs: Vec<[&String; 2]> = words.iter().array_windows().collect();
+    /// assert_eq!(pairs[2], [&words[2], &words[3]]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `N` is zero.
+    fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+        where Self: Sized, Self::Item: Copy
+    {
+        ArrayWindows::new(self)
+    }
+
+    /// Yields windows of `storage.window_size()` elements, each starting `step` elements after
+    /// the previous one. This is [Adaptor::emit_every](struct.Adaptor.html#method.emit_every):
+    /// a step larger than the window size skips the elements between the windows, which are
+    /// never buffered.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(5);
+    /// let starts: Vec<u32> = (0..12).sliding_windows_step(&mut storage, 2)
+    ///     .map(|window| *window.iter().next().unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(starts, [0, 2, 4, 6]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `step` is 0.
+    fn sliding_windows_step(self, storage: &mut Storage<Self::Item>, step: usize) -> Adaptor<'_, Self>
+        where Self: Sized
+    {
+        assert!(step > 0, "step must be positive");
+        Adaptor::new(self, storage).emit_every(step)
+    }
+
+    /// Yields non-overlapping windows of `storage.window_size()` elements, i.e. chunks, as
+    /// `Window`s of the reused storage instead of allocated `Vec`s. This is
+    /// [sliding_windows_step](#method.sliding_windows_step) with the window size as step.
+    ///
+    /// A trailing chunk with less elements is dropped, unless it is requested with
+    /// [Adaptor::with_partial_suffix](struct.Adaptor.html#method.with_partial_suffix).
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let chunks: Vec<Vec<u32>> = (0..8).tumbling_windows(&mut storage)
+    ///     .with_partial_suffix(true)
+    ///     .map(|window| window.iter().cloned().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(chunks, [vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+    /// ```
+    fn tumbling_windows(self, storage: &mut Storage<Self::Item>) -> Adaptor<'_, Self>
+        where Self: Sized
+    {
+        // a window size of 0 yields nothing anyway
+        let step = storage.window_size().max(1);
+        Adaptor::new(self, storage).emit_every(step)
+    }
+
+    /// Yields sliding windows over the elements of `self` transformed by `f`.
+    ///
+    /// `f` runs once per element before it enters the storage, while mapping the windows
+    /// would process every element once for every window it appears in, i.e. up to
+    /// `window_size` times. Use it for parsing or other expensive conversions:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let lines = ["1.5", "2.5", "4.0", "1.0"];
+    /// let mut storage: Storage<f64> = Storage::new(2);
+    /// let sums: Vec<f64> = lines.iter()
+    ///     .sliding_windows_map(&mut storage, |line| line.parse::<f64>().unwrap())
+    ///     .map(|window| window.iter().sum())
+    ///     .collect();
+    ///
+    /// assert_eq!(sums, [4.0, 6.5, 5.0]);
+    /// ```
+    fn sliding_windows_map<T, F>(self, storage: &mut Storage<T>, f: F) -> Adaptor<'_, Map<Self, F>>
+        where Self: Sized, F: FnMut(Self::Item) -> T
+    {
+        Adaptor::new(self.map(f), storage)
+    }
+
+    /// Yields sliding windows over the elements of `self` paired with a value `derive`d from
+    /// each element when it enters the storage, e.g. a number parsed from a log line.
+    ///
+    /// Like [sliding_windows_map](#method.sliding_windows_map), `derive` runs once per element
+    /// instead of once per window, but the windows keep the original elements as well. Both
+    /// are available through [Window::originals](struct.Window.html#method.originals) and
+    /// [Window::derived](struct.Window.html#method.derived).
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let lines = ["GET 120", "GET 80", "POST 400", "GET 100"];
+    /// let mut storage: Storage<(&str, u32)> = Storage::new(3);
+    /// let slowest: Vec<&str> = lines.iter().cloned()
+    ///     .sliding_windows_paired(&mut storage, |line| line.split(' ').nth(1).unwrap().parse().unwrap())
+    ///     .map(|window| *window.originals().zip(window.derived()).max_by_key(|&(_, ms)| ms).unwrap().0)
+    ///     .collect();
+    ///
+    /// assert_eq!(slowest, ["POST 400", "POST 400"]);
+    /// ```
+    fn sliding_windows_paired<U, F>(self, storage: &mut Storage<(Self::Item, U)>, derive: F)
+        -> Adaptor<'_, Paired<Self, F>>
+        where Self: Sized, F: FnMut(&Self::Item) -> U
+    {
+        Adaptor::new(Paired::new(self, derive), storage)
+    }
+
+    /// Yields sliding windows over a stream with missing elements, i.e. of `Option<T>`.
+    /// The `policy` decides whether `None`s are dropped or replaced before windowing.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, NullPolicy, Storage};
+    ///
+    /// let readings = vec![Some(1), None, Some(3), None];
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    ///
+    /// let mut sums = |policy| -> Vec<u32> {
+    ///     readings.iter().cloned()
+    ///         .sliding_windows_nulls(&mut storage, policy)
+    ///         .map(|window| window.iter().sum())
+    ///         .collect()
+    /// };
+    ///
+    /// assert_eq!(sums(NullPolicy::Skip), [4]);
+    /// assert_eq!(sums(NullPolicy::ForwardFill), [2, 4, 6]);
+    /// assert_eq!(sums(NullPolicy::Constant(0)), [1, 3, 3]);
+    /// ```
+    fn sliding_windows_nulls<T>(self, storage: &mut Storage<T>, policy: NullPolicy<T>) -> Adaptor<'_, FillNulls<Self, T>>
+        where Self: Sized + Iterator<Item = Option<T>>, T: Clone
+    {
+        Adaptor::new(FillNulls::new(self, policy), storage)
+    }
+
+    /// Replaces missing elements with the most recent observed value (last observation carried
+    /// forward). Missing elements before the first observed value stay missing.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let filled: Vec<Option<u32>> = vec![None, Some(1), None, Some(2), None].into_iter().ffill().collect();
+    /// assert_eq!(filled, [None, Some(1), Some(1), Some(2), Some(2)]);
+    /// ```
+    fn ffill<T>(self) -> ForwardFill<Self, T>
+        where Self: Sized + Iterator<Item = Option<T>>, T: Clone
+    {
+        ForwardFill::new(self)
+    }
+
+    /// Replaces missing elements with the next observed value. Missing elements after the last
+    /// observed value stay missing.
+    ///
+    /// The lookahead only counts the missing elements, so long gaps don't allocate.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let filled: Vec<Option<u32>> = vec![None, Some(1), None, Some(2), None].into_iter().bfill().collect();
+    /// assert_eq!(filled, [Some(1), Some(1), Some(2), Some(2), None]);
+    /// ```
+    fn bfill<T>(self) -> BackwardFill<Self, T>
+        where Self: Sized + Iterator<Item = Option<T>>, T: Clone
+    {
+        BackwardFill::new(self)
+    }
+
+    /// Yields sliding windows within every run of consecutive elements with the same `key`,
+    /// e.g. per file or per session of a concatenated stream. The window starts over at every
+    /// key change, see [Adaptor::reset_on_key_change](struct.Adaptor.html#method.reset_on_key_change).
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let lines = vec![("a.txt", 1), ("a.txt", 2), ("a.txt", 3), ("b.txt", 4), ("b.txt", 5)];
+    /// let mut storage = Storage::new(2);
+    /// let pairs: Vec<(u32, u32)> = lines.into_iter()
+    ///     .run_windows_by_key(&mut storage, |&(file, _)| file)
+    ///     .map(|window| {
+    ///         let mut iter = window.iter().map(|&(_, line)| line);
+    ///         (iter.next().unwrap(), iter.next().unwrap())
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(pairs, [(1, 2), (2, 3), (4, 5)]);
+    /// ```
+    fn run_windows_by_key<'a, K, F>(self, storage: &'a mut Storage<Self::Item>, key: F) -> Adaptor<'a, Self>
+        where Self: Sized, F: FnMut(&Self::Item) -> K + 'a, K: PartialEq + 'a
+    {
+        Adaptor::new(self, storage).reset_on_key_change(key)
+    }
+
+    /// Groups runs of consecutive elements with the same `bucket` key, e.g. the minute of a
+    /// timestamp, and yields the key together with one `Window` per run.
+    /// A bucket is flushed when the key changes, so keys should be non-decreasing.
+    ///
+    /// The window size of `storage` is ignored, its allocation grows to the largest bucket
+    /// and is reused for all buckets.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let events = vec![(0, 5), (30, 1), (61, 2), (150, 4), (170, 2)];
+    /// let mut storage = Storage::default();
+    /// let per_minute: Vec<(u32, u32)> = events.into_iter()
+    ///     .tumbling_by(&mut storage, |&(seconds, _)| seconds / 60)
+    ///     .map(|(minute, window)| (minute, window.iter().map(|&(_, x)| x).sum()))
+    ///     .collect();
+    ///
+    /// assert_eq!(per_minute, [(0, 6), (1, 2), (2, 6)]);
+    /// ```
+    fn tumbling_by<K, F>(self, storage: &mut Storage<Self::Item>, bucket: F) -> TumblingBy<'_, Self, K, F>
+        where Self: Sized, K: PartialEq, F: FnMut(&Self::Item) -> K
+    {
+        TumblingBy::new(self, storage, bucket)
+    }
+
+    /// Yields the sum of the trailing `window_size` elements, updated incrementally.
+    ///
+    /// For long floating-point streams use compensated summation and/or periodic
+    /// recomputation to keep the sum from drifting.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Summation};
+    ///
+    /// let sums: Vec<f64> = (0..6).rolling_sum(3).summation(Summation::Compensated).collect();
+    /// assert_eq!(sums, [3.0, 6.0, 9.0, 12.0]);
+    /// ```
+    fn rolling_sum(self, window_size: usize) -> RollingSum<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        RollingSum::new(self, window_size)
+    }
+
+    /// Yields the mean of the trailing `window_size` elements, updated incrementally.
+    /// It offers the same precision options as [rolling_sum](#method.rolling_sum).
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let means: Vec<f64> = [1.0, 2.0, 6.0, 1.0].iter().cloned().rolling_mean(2).recompute_every(100).collect();
+    /// assert_eq!(means, [1.5, 4.0, 3.5]);
+    /// ```
+    fn rolling_mean(self, window_size: usize) -> RollingMean<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        RollingMean::new(self, window_size)
+    }
+
+    /// Yields the sum of the non-NaN elements of the trailing `window_size` elements, or `None`
+    /// if the window holds less than [min_periods](struct.RollingSumSkipNan.html#method.min_periods)
+    /// non-NaN elements (1 by default). The number of valid elements is tracked incrementally.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let data = [1.0, f64::NAN, 2.0, f64::NAN, f64::NAN];
+    /// let sums: Vec<Option<f64>> = data.iter().cloned().rolling_sum_skipnan(2).collect();
+    /// assert_eq!(sums, [Some(1.0), Some(2.0), Some(2.0), None]);
+    /// ```
+    fn rolling_sum_skipnan(self, window_size: usize) -> RollingSumSkipNan<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        RollingSumSkipNan::new(self, window_size)
+    }
+
+    /// Yields the mean of the non-NaN elements of the trailing `window_size` elements, like
+    /// [rolling_sum_skipnan](#method.rolling_sum_skipnan).
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let data = [1.0, f64::NAN, 3.0, 5.0];
+    /// let means: Vec<Option<f64>> = data.iter().cloned().rolling_mean_skipnan(3).min_periods(2).collect();
+    /// assert_eq!(means, [Some(2.0), Some(4.0)]);
+    /// ```
+    fn rolling_mean_skipnan(self, window_size: usize) -> RollingMeanSkipNan<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        RollingMeanSkipNan::new(self, window_size)
+    }
+
+    /// Yields the exact sums of the trailing `window_size` integers as
+    /// [ExactStats](struct.ExactStats.html), from which the mean and variance are available
+    /// as exact [Ratio](struct.Ratio.html)s. No floating-point arithmetic is involved, so the
+    /// results are reproducible.
+    ///
+    /// The sums are kept as `i128`. Integers of up to 32 bits never overflow them, larger
+    /// values may, e.g. the square of `u64::MAX` doesn't fit.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Ratio};
+    ///
+    /// let stats: Vec<_> = [1, 2, 4, 4].iter().cloned().rolling_exact_stats(3).collect();
+    ///
+    /// assert_eq!(stats[0].mean(), Ratio::new(7, 3));
+    /// assert_eq!(stats[1].variance(), Ratio::new(8, 9));
+    /// assert_eq!(stats[1].sample_variance(), Some(Ratio::new(4, 3)));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the sums overflow, the statistics are never silently wrong.
+    fn rolling_exact_stats(self, window_size: usize) -> RollingExactStats<Self>
+        where Self: Sized, Self::Item: Into<i128>
+    {
+        RollingExactStats::new(self, window_size)
+    }
+
+    /// Consumes `self` and `other` in lockstep and yields the sample covariance of the
+    /// trailing `window_size` pairs. The required sums are maintained incrementally.
+    ///
+    /// Iteration stops as soon as one of the streams is exhausted.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let a = [1.0, 2.0, 3.0, 4.0];
+    /// let b = [2.0, 4.0, 6.0, 0.0];
+    /// let cov: Vec<f64> = a.iter().cloned().rolling_cov(b.iter().cloned(), 3).collect();
+    ///
+    /// assert!((cov[0] - 2.0).abs() < 1e-12);
+    /// assert!((cov[1] + 2.0).abs() < 1e-12);
+    /// ```
+    fn rolling_cov<J>(self, other: J, window_size: usize) -> RollingCov<Self, J::IntoIter>
+        where Self: Sized, Self::Item: WindowNum, J: IntoIterator, J::Item: WindowNum
+    {
+        RollingCov::new(self, other.into_iter(), window_size)
+    }
+
+    /// Consumes `self` and `other` in lockstep and yields the pearson correlation of the
+    /// trailing `window_size` pairs. The required sums are maintained incrementally.
+    ///
+    /// The correlation is `NaN` for windows in which one of the streams is constant.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let a = [1.0, 2.0, 3.0, 4.0];
+    /// let b = [2.0, 4.0, 6.0, 0.0];
+    /// let corr: Vec<f64> = a.iter().cloned().rolling_corr(b.iter().cloned(), 3).collect();
+    ///
+    /// assert!((corr[0] - 1.0).abs() < 1e-12);
+    /// assert!(corr[1] < 0.0);
+    /// ```
+    fn rolling_corr<J>(self, other: J, window_size: usize) -> RollingCorr<Self, J::IntoIter>
+        where Self: Sized, Self::Item: WindowNum, J: IntoIterator, J::Item: WindowNum
+    {
+        RollingCorr::new(self, other.into_iter(), window_size)
+    }
+
+    /// Yields the least-squares [Trend](struct.Trend.html) (slope, intercept and R²) of the
+    /// trailing `window_size` elements against their index inside of the window.
+    /// The required sums are maintained incrementally.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let trends: Vec<_> = [1u8, 3, 5, 4].iter().cloned().rolling_trend(3).collect();
+    ///
+    /// assert_eq!(trends[0].slope, 2.0);
+    /// assert_eq!(trends[0].intercept, 1.0);
+    /// assert_eq!(trends[1].slope, 0.5);
+    /// ```
+    fn rolling_trend(self, window_size: usize) -> RollingTrend<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        RollingTrend::new(self, window_size)
+    }
+
+    /// Yields a [ChangePoint](detect/struct.ChangePoint.html) for every element flagged by
+    /// `detector`. See the [detect](detect/index.html) module for more information.
+    fn detect(self, detector: Detector) -> Detections<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        Detections::new(self, detector)
+    }
+
+    /// Yields a [Skew](enum.Skew.html) for every pair of consecutive timestamps where the
+    /// later one goes backwards or jumps ahead by more than `max_jump`, a common data quality
+    /// check for event streams. Timestamps equal to the previous one are fine.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Skew};
+    ///
+    /// let timestamps = [100u64, 101, 103, 102, 160, 161];
+    /// let skews: Vec<Skew<u64>> = timestamps.iter().cloned().timestamp_skew(10).collect();
+    ///
+    /// assert_eq!(skews, [Skew::Backwards { index: 3, delta: 1 }, Skew::Jump { index: 4, delta: 58 }]);
+    /// ```
+    fn timestamp_skew<D>(self, max_jump: D) -> TimestampSkew<Self, D>
+        where Self: Sized, Self::Item: Copy + PartialOrd + Sub<Output = D>, D: PartialOrd
+    {
+        TimestampSkew::new(self, max_jump)
+    }
+
+    /// Yields a debounced state for every element. The state starts out `false`, becomes `true`
+    /// once at least `quorum` of the trailing `window_size` elements satisfy `predicate` and
+    /// becomes `false` again once at least `quorum` of them don't. Otherwise it is unchanged.
+    ///
+    /// Use `quorum == window_size` to require the whole window to agree.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let noisy = [0, 1, 0, 1, 1, 1, 0, 1, 0, 0, 0];
+    /// let state: Vec<bool> = noisy.iter().debounce(3, 3, |&&x| x == 1).collect();
+    ///
+    /// assert_eq!(state, [false, false, false, false, false, true, true, true, true, true, false]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `quorum` is zero or larger than `window_size`.
+    fn debounce<F>(self, window_size: usize, quorum: usize, predicate: F) -> Debounce<Self, F>
+        where Self: Sized, F: FnMut(&Self::Item) -> bool
+    {
+        Debounce::new(self, window_size, quorum, predicate)
+    }
+
+    /// Yields a boolean state with hysteresis (a Schmitt trigger) for every element. The state
+    /// starts out `false`, becomes `true` once an element reaches `high` and `false` again
+    /// once an element drops to `low`. Elements in between keep the state.
+    ///
+    /// Applied to a rolling statistic like [rolling_mean](#method.rolling_mean), this turns a
+    /// noisy sensor reading into a stable on/off signal:
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let temperature = [20, 24, 19, 25, 26, 24, 22, 23, 18, 21];
+    /// let heating_off: Vec<bool> = temperature.iter().cloned()
+    ///     .rolling_mean(2)
+    ///     .hysteresis(21.0, 24.0)
+    ///     .collect();
+    ///
+    /// // the means are 22, 21.5, 22, 25.5, 25, 23, 22.5, 20.5, 19.5
+    /// assert_eq!(heating_off, [false, false, false, true, true, true, true, false, false]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `low` is larger than `high` or either is NaN.
+    fn hysteresis(self, low: f64, high: f64) -> Hysteresis<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        Hysteresis::new(self, low, high)
+    }
+
+    /// Yields the most common element of the trailing `window_size` elements, using `tie` to
+    /// choose between equally common ones. The counts and the most recent of the most common
+    /// elements are maintained incrementally.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, TiePolicy};
+    ///
+    /// let labels = ["a", "a", "b", "a", "b", "b", "b"];
+    /// let smoothed: Vec<&str> = labels.iter().cloned().rolling_majority(3, TiePolicy::MostRecent).collect();
+    ///
+    /// assert_eq!(smoothed, ["a", "a", "b", "b", "b"]);
+    /// ```
+    fn rolling_majority(self, window_size: usize, tie: TiePolicy) -> RollingMajority<Self>
+        where Self: Sized, Self::Item: Hash + Eq + Clone
+    {
+        RollingMajority::new(self, window_size, tie)
+    }
+
+    /// Yields the `k` largest elements of every window of `window_size` elements by `key`,
+    /// largest first. Of elements with equal keys the most recent one comes first.
+    ///
+    /// The window is kept ordered, so every step costs `O(log(window_size) + k)`.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let trades = [("a", 5), ("b", 9), ("c", 2), ("d", 7), ("e", 1)];
+    /// let top: Vec<Vec<&str>> = trades.iter()
+    ///     .rolling_top_k_by_key(3, 2, |trade| trade.1)
+    ///     .map(|top| top.iter().map(|trade| trade.0).collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(top, [["b", "a"], ["b", "d"], ["d", "c"]]);
+    /// ```
+    fn rolling_top_k_by_key<K, F>(self, window_size: usize, k: usize, key: F) -> RollingTopK<Self, K, F>
+        where Self: Sized, Self::Item: Clone, K: Ord + Clone, F: FnMut(&Self::Item) -> K
+    {
+        RollingTopK::new(self, window_size, k, key)
+    }
+
+    /// Drops every element which equals one of the preceding `window_size` elements, e.g.
+    /// repeated log lines or events delivered more than once. Dropped elements still count
+    /// as seen, see [RecentSet](struct.RecentSet.html).
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let events = ["login", "click", "login", "click", "scroll", "logout", "login"];
+    /// let deduped: Vec<&str> = events.iter().cloned().dedup_within(3).collect();
+    ///
+    /// assert_eq!(deduped, ["login", "click", "scroll", "logout", "login"]);
+    /// ```
+    fn dedup_within(self, window_size: usize) -> DedupWithin<Self>
+        where Self: Sized, Self::Item: Hash + Eq + Clone
+    {
+        DedupWithin::new(self, window_size)
+    }
+
+    /// Like [dedup_within](#method.dedup_within), but tracks the window approximately with
+    /// an [ApproxRecentSet](struct.ApproxRecentSet.html), whose memory doesn't depend on the
+    /// size of the elements. An element is dropped wrongly with probability
+    /// `false_positive_rate`, or if it was seen up to `2 * window_size` elements before.
+    ///
+    /// Only available with the `bloom` feature.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// // the ids 9000 to 9999 are delivered twice
+    /// let ids = (0..10_000u32).chain(9_000..11_000);
+    /// let unique = ids.dedup_within_approx(10_000, 0.001).count();
+    ///
+    /// // a few unique ids may be dropped as false positives
+    /// assert!(unique <= 11_000 && unique > 10_950);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `false_positive_rate` is not in the open interval `(0, 1)`.
+    #[cfg(feature = "bloom")]
+    fn dedup_within_approx(self, window_size: usize, false_positive_rate: f64) -> ApproxDedupWithin<Self>
+        where Self: Sized, Self::Item: Hash
+    {
+        ApproxDedupWithin::new(self, window_size, false_positive_rate)
+    }
+
+    /// Splits the elements into disjoint chunks of `n` elements and yields the result of
+    /// `reducer` for every chunk, e.g. the mean, the maximum or the last element.
+    /// A trailing chunk with less than `n` elements is dropped.
+    ///
+    /// The chunk buffer is allocated once and reused.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let maxima: Vec<u32> = (0..10).decimate(3, |chunk| *chunk.iter().max().unwrap()).collect();
+    /// assert_eq!(maxima, [2, 5, 8]);
+    /// ```
+    fn decimate<U, F>(self, n: usize, reducer: F) -> Decimate<Self, F>
+        where Self: Sized, F: FnMut(&[Self::Item]) -> U
+    {
+        Decimate::new(self, n, reducer)
+    }
+
+    /// Yields the bits of a byte iterator, most significant bit first.
+    ///
+    /// Combined with [sliding_windows](#method.sliding_windows) this yields windows of bools
+    /// which don't have to align to byte boundaries, see [bit_windows](#method.bit_windows)
+    /// for windows as integer masks.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<bool> = Storage::new(3);
+    /// let runs = [0b1110_0001u8].iter().cloned().bits()
+    ///     .sliding_windows(&mut storage)
+    ///     .filter(|window| window.all(|&bit| bit) || window.all(|&bit| !bit))
+    ///     .count();
+    ///
+    /// assert_eq!(runs, 3);
+    /// ```
+    fn bits(self) -> Bits<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        Bits::new(self)
+    }
+
+    /// Yields sliding windows of `window_size` bits over the bits of a byte iterator, most
+    /// significant bit first, as integer masks. The newest bit is the least significant bit
+    /// of the mask, so a window reads like the bits in stream order written as a binary
+    /// number. This is useful to find sync words which don't align to byte boundaries.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// // the sync word 0b1011_0110 starts at bit 4
+    /// let stream = [0b0000_1011u8, 0b0110_0000];
+    /// let position = stream.iter().cloned().bit_windows::<u8>(8).position(|w| w == 0b1011_0110);
+    ///
+    /// assert_eq!(position, Some(4));
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `window_size` is zero or exceeds the number of bits of `M`.
+    fn bit_windows<M: BitMask>(self, window_size: usize) -> BitWindows<Self, M>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        BitWindows::new(self, window_size)
+    }
+
+    /// Yields the bit offsets into a byte stream where `pattern` starts, allowing up to
+    /// `max_bit_errors` flipped bits. The pattern is matched at every bit offset, not just at
+    /// byte boundaries, which makes this a sync word detector for telemetry or radio frames.
+    ///
+    /// Overlapping matches are all yielded.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// // the CCSDS sync word, shifted by 3 bits and with one flipped bit
+    /// let sync = [0x1a, 0xcf, 0xfc, 0x1d];
+    /// let stream = [0b0000_0011u8, 0b0101_1001, 0b1111_1011, 0b1000_0011, 0b1010_0000];
+    ///
+    /// assert_eq!(stream.iter().cloned().find_sync(&sync, 0).count(), 0);
+    /// assert_eq!(stream.iter().cloned().find_sync(&sync, 1).collect::<Vec<_>>(), [3]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `pattern` is empty or longer than 16 bytes.
+    fn find_sync(self, pattern: &[u8], max_bit_errors: u32) -> FindSync<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        FindSync::new(self, pattern, max_bit_errors)
+    }
+
+    /// Sliding windows of `window_size` chars as `&str`, for text processing without a
+    /// tokenizer. All windows are slices of a single reused `String`, so no window is
+    /// allocated. Use [sliding_windows](#method.sliding_windows) for windows of `char`s.
+    ///
+    /// Since the windows borrow the returned `CharWindows`, it is no `Iterator`, windows are
+    /// taken with [next_window](struct.CharWindows.html#method.next_window).
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let mut windows = "héllo".chars().char_windows(3);
+    /// let mut trigrams = Vec::new();
+    /// while let Some(window) = windows.next_window() {
+    ///     trigrams.push(window.to_owned());
+    /// }
+    ///
+    /// assert_eq!(trigrams, ["hél", "éll", "llo"]);
+    /// ```
+    fn char_windows(self, window_size: usize) -> CharWindows<Self>
+        where Self: Sized + Iterator<Item = char>
+    {
+        CharWindows::new(self, window_size)
+    }
+
+    /// Yields the offsets into a byte stream where `needle` starts, like `memmem` but without
+    /// buffering more than `needle.len()` bytes, so a file can be searched while it is read.
+    ///
+    /// The windows are compared by a rolling hash, only windows with the hash of `needle`
+    /// are compared byte by byte. Overlapping matches are all yielded.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let haystack = b"abracadabra";
+    /// let offsets: Vec<usize> = haystack.iter().cloned().find_subsequence(b"abra").collect();
+    ///
+    /// assert_eq!(offsets, [0, 7]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `needle` is empty.
+    fn find_subsequence(self, needle: &[u8]) -> FindSubsequence<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        FindSubsequence::new(self, needle)
+    }
+
+    /// Yields the Hamming distance, the number of differing positions, between every window
+    /// of `reference.len()` elements and `reference`.
+    ///
+    /// Every window is compared element by element, the window shifts its alignment against
+    /// the reference, so there is no cheaper incremental update. For bit streams
+    /// [find_sync](#method.find_sync) compares whole windows with a single XOR instead.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let distances: Vec<usize> = "GATTACA".chars().hamming_distance(&['T', 'A', 'C']).collect();
+    /// assert_eq!(distances, [2, 3, 2, 0, 3]);
+    /// ```
+    fn hamming_distance(self, reference: &[Self::Item]) -> HammingDistance<'_, Self>
+        where Self: Sized, Self::Item: PartialEq
+    {
+        HammingDistance::new(self, reference)
+    }
+
+    /// Yields the edit (Levenshtein) distance between every window of `reference.len()`
+    /// elements and `reference` if it is at most `band`, and `None` otherwise.
+    ///
+    /// Only alignments which shift the window by at most `band` positions against the
+    /// reference are considered, so every window costs `O(reference.len() * band)`.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let reference: Vec<char> = "ACGT".chars().collect();
+    /// let distances: Vec<Option<usize>> = "TACGGTT".chars().edit_distance(&reference, 2).collect();
+    ///
+    /// // "TACG" is a deletion and an insertion away, "GGTT" needs three edits
+    /// assert_eq!(distances, [Some(2), Some(1), Some(2), None]);
+    /// ```
+    fn edit_distance(self, reference: &[Self::Item], band: usize) -> EditDistance<'_, Self>
+        where Self: Sized, Self::Item: PartialEq
+    {
+        EditDistance::new(self, reference, band)
+    }
+
+    /// Yields the Shannon entropy in bits per byte, between 0 and 8, of every window of
+    /// `window_size` bytes. High entropy regions of a binary hint at compressed or encrypted
+    /// data.
+    ///
+    /// The byte counts of the window are updated incrementally, so every window costs `O(1)`.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let data = [0u8, 0, 1, 1, 2, 3, 4];
+    /// let entropy: Vec<f64> = data.iter().cloned().rolling_entropy(4).collect();
+    ///
+    /// assert_eq!(entropy, [1.0, 1.5, 1.5, 2.0]);
+    /// ```
+    fn rolling_entropy(self, window_size: usize) -> RollingEntropy<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        RollingEntropy::new(self, window_size)
+    }
+
+    /// Yields the Adler-32 checksum of every window of `window_size` bytes, e.g. to
+    /// fingerprint fixed size blocks at every offset of a stream.
+    ///
+    /// The checksum is rolled forward, so every window costs `O(1)`.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let checksums: Vec<u32> = b"xWikipedia".iter().cloned().rolling_adler32(9).collect();
+    /// assert_eq!(checksums[1], 0x11e6_0398);
+    /// ```
+    fn rolling_adler32(self, window_size: usize) -> RollingAdler32<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        RollingAdler32::new(self, window_size)
+    }
+
+    /// Yields the CRC-32 checksum (as used by zlib, gzip and PNG) of every window of
+    /// `window_size` bytes.
+    ///
+    /// The byte leaving the window is removed with a precomputed table, so every window
+    /// costs `O(1)`. Creating the adaptor costs `O(window_size)`.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let checksums: Vec<u32> = b"0123456789".iter().cloned().rolling_crc32(9).collect();
+    /// assert_eq!(checksums, [0x37fa_d1ba, 0xcbf4_3926]);
+    /// ```
+    fn rolling_crc32(self, window_size: usize) -> RollingCrc32<Self>
+        where Self: Sized + Iterator<Item = u8>
+    {
+        RollingCrc32::new(self, window_size)
+    }
+
+    /// Yields the maximal runs of equal consecutive elements with their start position and
+    /// length (run-length encoding), see [runs_by](#method.runs_by).
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Run};
+    ///
+    /// let runs: Vec<Run<char>> = "aaabcc".chars().runs().collect();
+    /// assert_eq!(runs, [
+    ///     Run { value: 'a', start: 0, len: 3 },
+    ///     Run { value: 'b', start: 3, len: 1 },
+    ///     Run { value: 'c', start: 4, len: 2 },
+    /// ]);
+    /// ```
+    fn runs(self) -> Runs<Self, EqFn<Self::Item>>
+        where Self: Sized, Self::Item: PartialEq
+    {
+        Runs::new(self, PartialEq::eq)
+    }
+
+    /// Yields the maximal runs of consecutive elements `same` considers equivalent, e.g. the
+    /// plateaus of a noisy signal. Every element is compared with the first element of its
+    /// run, which is yielded as [Run::value](struct.Run.html#structfield.value), so slowly
+    /// drifting values don't stay in one run.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let signal = [1.0f64, 1.1, 0.9, 5.0, 5.2, 5.4, 1.0];
+    /// let plateaus: Vec<(usize, usize)> = signal.iter()
+    ///     .runs_by(|a, b| (*a - *b).abs() <= 0.3)
+    ///     .filter(|run| run.len > 1)
+    ///     .map(|run| (run.start, run.len))
+    ///     .collect();
+    ///
+    /// assert_eq!(plateaus, [(0, 3), (3, 2)]);
+    /// ```
+    fn runs_by<F>(self, same: F) -> Runs<Self, F>
+        where Self: Sized, F: FnMut(&Self::Item, &Self::Item) -> bool
+    {
+        Runs::new(self, same)
+    }
+
+    /// Labels every element as a local minimum, a local maximum or neither by comparing it
+    /// with its neighbours up to `radius` positions before and after it. Near the ends of
+    /// the input fewer neighbours are available, ties are labeled as neither.
+    ///
+    /// ```
+    /// use sliding_windows::{Extremum, IterExt};
+    /// use sliding_windows::Extremum::{Max, Min, Neither};
+    ///
+    /// let labels: Vec<Extremum> = [3, 1, 2, 5, 4, 4].iter().label_extrema(1).collect();
+    /// assert_eq!(labels, [Max, Min, Neither, Max, Neither, Neither]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `radius` is zero.
+    fn label_extrema(self, radius: usize) -> LabelExtrema<Self>
+        where Self: Sized, Self::Item: PartialOrd
+    {
+        LabelExtrema::new(self, radius)
+    }
+
+    /// Two level aggregation: reduces disjoint chunks of `inner_size` elements like
+    /// [decimate](#method.decimate) and yields sliding windows over the reduced values,
+    /// e.g. 60 second windows over 1 second means. The outer window size is the window size
+    /// of `storage`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let peaks: Vec<u32> = (0..12)
+    ///     .rollup(3, |chunk| chunk.iter().sum::<u32>(), &mut storage)
+    ///     .map(|window| *window.iter().max().unwrap())
+    ///     .collect();
+    ///
+    /// // the chunk sums are 3, 12, 21 and 30
+    /// assert_eq!(peaks, [12, 21, 30]);
+    /// ```
+    fn rollup<U, F>(self, inner_size: usize, reducer: F, storage: &mut Storage<U>) -> Adaptor<'_, Decimate<Self, F>>
+        where Self: Sized, F: FnMut(&[Self::Item]) -> U
+    {
+        Adaptor::new(self.decimate(inner_size, reducer), storage)
+    }
+
+    /// Aggregates an irregularly timestamped stream into one value per `interval`, so that
+    /// fixed size windows downstream span fixed time spans. Elements are grouped by
+    /// `timestamp(elt) / interval` and every group is reduced by `reducer`. Intervals without
+    /// elements are handled according to `empty`.
+    ///
+    /// Yields the start of every interval together with its value. The element buffer is
+    /// reused for all intervals.
+    ///
+    /// ```
+    /// use sliding_windows::{EmptyBucket, IterExt, Storage};
+    ///
+    /// // (milliseconds, value)
+    /// let events = vec![(10, 1), (950, 2), (1100, 5), (3500, 1)];
+    /// let per_second: Vec<(u64, usize)> = events.into_iter()
+    ///     .time_buckets(1000, |&(t, _)| t, |bucket| bucket.len(), EmptyBucket::Fill(0))
+    ///     .collect();
+    /// assert_eq!(per_second, [(0, 2), (1000, 1), (2000, 0), (3000, 1)]);
+    ///
+    /// // events in the trailing two seconds
+    /// let mut storage: Storage<usize> = Storage::new(2);
+    /// let counts: Vec<usize> = per_second.into_iter()
+    ///     .map(|(_, count)| count)
+    ///     .sliding_windows(&mut storage)
+    ///     .map(|window| window.iter().sum())
+    ///     .collect();
+    /// assert_eq!(counts, [3, 1, 1]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `interval` is 0 or if the timestamps decrease across intervals.
+    fn time_buckets<U, F, R>(self, interval: u64, timestamp: F, reducer: R, empty: EmptyBucket<U>) -> TimeBuckets<Self, F, R, U>
+        where Self: Sized, U: Clone, F: FnMut(&Self::Item) -> u64, R: FnMut(&[Self::Item]) -> U
+    {
+        TimeBuckets::new(self, interval, timestamp, reducer, empty)
+    }
+
+    /// Downsamples `(x, y)` points to `threshold` visually representative points using the
+    /// Largest-Triangle-Three-Buckets algorithm. The first and the last point are always kept.
+    ///
+    /// The points are processed bucket by bucket, so only two buckets are buffered at a time.
+    /// If `threshold` is less than 3 or not less than the number of points, all points are yielded.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// let ys = [0.0, 1.0, 0.0, 9.0, 0.0, 1.0, 0.0, -9.0, 0.0, 1.0];
+    /// let points = ys.iter().enumerate().map(|(x, &y)| (x as u32, y));
+    /// let xs: Vec<u32> = points.lttb(4).map(|(x, _)| x).collect();
+    ///
+    /// assert_eq!(xs, [0, 3, 7, 9]);
+    /// ```
+    fn lttb<X, Y>(self, threshold: usize) -> Lttb<Self>
+        where Self: Sized + ExactSizeIterator<Item = (X, Y)>, X: WindowNum, Y: WindowNum
+    {
+        Lttb::new(self, threshold)
+    }
+
+    /// Converts a stream sampled at `src_rate` into one sampled at `dst_rate` by interpolating
+    /// between the neighbouring input samples. Only the last four input samples are kept.
+    ///
+    /// The first output sample coincides with the first input sample and the output ends at the
+    /// last input sample.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Interpolation};
+    ///
+    /// let up: Vec<f64> = [0.0, 1.0, 4.0].iter().cloned().resample(1.0, 2.0, Interpolation::Linear).collect();
+    /// assert_eq!(up, [0.0, 0.5, 1.0, 2.5, 4.0]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if one of the rates is not positive.
+    fn resample(self, src_rate: f64, dst_rate: f64, interpolation: Interpolation) -> Resample<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        Resample::new(self, src_rate, dst_rate, interpolation)
+    }
+
+    /// Yields the DFT coefficient `bin` of the trailing `window_size` elements, the oldest
+    /// element being at time 0. Once the window is full the coefficient is updated in O(1)
+    /// per element instead of being recomputed in O(window_size).
+    ///
+    /// As every update rotates the previous value, rounding errors accumulate slowly over
+    /// very long streams.
+    ///
+    /// ```
+    /// use sliding_windows::IterExt;
+    ///
+    /// // a cosine with 2 periods per 8 samples
+    /// let signal = (0..32).map(|n| (std::f64::consts::PI * n as f64 / 2.0).cos());
+    ///
+    /// for bin in signal.sliding_dft(2, 8) {
+    ///     assert!((bin.norm() - 4.0).abs() < 1e-9);
+    /// }
+    /// ```
+    fn sliding_dft(self, bin: usize, window_size: usize) -> SlidingDft<Self>
+        where Self: Sized, Self::Item: WindowNum
+    {
+        SlidingDft::new(self, bin, window_size)
+    }
 }
 
 impl<T: ?Sized> IterExt for T where T: Iterator { }
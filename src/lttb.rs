@@ -0,0 +1,132 @@
+use std::mem;
+
+use num::WindowNum;
+
+/// Iterator yielding the points selected by the Largest-Triangle-Three-Buckets algorithm.
+///
+/// See [IterExt::lttb](trait.IterExt.html#method.lttb) for more information.
+pub struct Lttb<I: Iterator> {
+    iter: I,
+    len: usize,
+    threshold: usize,
+    // average bucket size
+    every: f64,
+    // number of points yielded so far
+    yielded: usize,
+    // elements of the bucket the next point is selected from and of the bucket after it
+    current: Vec<I::Item>,
+    upcoming: Vec<I::Item>,
+    // coordinates of the previously selected point
+    previous: (f64, f64),
+}
+
+impl<I, X, Y> Lttb<I>
+    where I: ExactSizeIterator<Item = (X, Y)>, X: WindowNum, Y: WindowNum
+{
+    /// This creates a new Lttb iterator. Usually you should be using
+    /// [IterExt::lttb](trait.IterExt.html#method.lttb).
+    pub fn new(iter: I, threshold: usize) -> Lttb<I> {
+        let len = iter.len();
+        let every = if threshold > 2 { (len as f64 - 2.0) / (threshold as f64 - 2.0) } else { 0.0 };
+
+        Lttb {
+            iter,
+            len,
+            threshold,
+            every,
+            yielded: 0,
+            current: Vec::new(),
+            upcoming: Vec::new(),
+            previous: (0.0, 0.0),
+        }
+    }
+
+    // no downsampling is necessary or possible
+    fn passthrough(&self) -> bool {
+        self.threshold >= self.len || self.threshold < 3
+    }
+
+    // index of the first element of the given bucket, the last bucket only holds the last point
+    fn bucket_start(&self, bucket: usize) -> usize {
+        if bucket >= self.threshold - 2 {
+            self.len - 1
+        } else {
+            ((bucket as f64 * self.every) as usize + 1).min(self.len - 1)
+        }
+    }
+
+    fn fill_upcoming(&mut self, bucket: usize) {
+        let n = if bucket >= self.threshold - 2 {
+            1
+        } else {
+            self.bucket_start(bucket + 1) - self.bucket_start(bucket)
+        };
+        self.upcoming.extend((&mut self.iter).take(n));
+    }
+
+    fn coords(point: &(X, Y)) -> (f64, f64) {
+        (point.0.to_f64(), point.1.to_f64())
+    }
+}
+
+impl<I, X, Y> Iterator for Lttb<I>
+    where I: ExactSizeIterator<Item = (X, Y)>, X: WindowNum, Y: WindowNum
+{
+    type Item = (X, Y);
+
+    fn next(&mut self) -> Option<(X, Y)> {
+        if self.passthrough() {
+            return self.iter.next();
+        }
+
+        let point = if self.yielded == 0 {
+            // the first point is always selected
+            let first = self.iter.next()?;
+            self.fill_upcoming(0);
+            first
+        } else if self.yielded < self.threshold - 1 {
+            // the upcoming bucket becomes the current one
+            mem::swap(&mut self.current, &mut self.upcoming);
+            self.upcoming.clear();
+            self.fill_upcoming(self.yielded);
+
+            let n = self.upcoming.len() as f64;
+            let avg_x = self.upcoming.iter().map(|p| Self::coords(p).0).sum::<f64>() / n;
+            let avg_y = self.upcoming.iter().map(|p| Self::coords(p).1).sum::<f64>() / n;
+            let (prev_x, prev_y) = self.previous;
+
+            // select the point spanning the largest triangle with its neighbours
+            let mut best = (0, -1.0);
+            for (i, point) in self.current.iter().enumerate() {
+                let (x, y) = Self::coords(point);
+                let area = ((prev_x - avg_x) * (y - prev_y) - (prev_x - x) * (avg_y - prev_y)).abs();
+                if area > best.1 {
+                    best = (i, area);
+                }
+            }
+
+            self.current.swap_remove(best.0)
+        } else if self.yielded == self.threshold - 1 {
+            // the last point is always selected
+            self.upcoming.pop()?
+        } else {
+            return None;
+        };
+
+        self.previous = Self::coords(&point);
+        self.yielded += 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.passthrough() {
+            return self.iter.size_hint();
+        }
+
+        let remaining = self.threshold - self.yielded;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<I, X, Y> ExactSizeIterator for Lttb<I>
+    where I: ExactSizeIterator<Item = (X, Y)>, X: WindowNum, Y: WindowNum {}
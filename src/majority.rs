@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use ring::Ring;
+
+/// How `RollingMajority` picks a label if several labels are the most common one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiePolicy {
+    /// Pick the tied label which occurred most recently.
+    MostRecent,
+    /// Keep the previously yielded label if it is one of the tied labels,
+    /// otherwise behave like `MostRecent`. This avoids flickering between tied labels.
+    KeepPrevious,
+}
+
+/// Iterator yielding the most common element of the trailing window.
+///
+/// See [IterExt::rolling_majority](trait.IterExt.html#method.rolling_majority) for more information.
+pub struct RollingMajority<I: Iterator> {
+    iter: I,
+    window: Ring<I::Item>,
+    // the count and the sequence number of the last occurrence of every label in the window
+    labels: HashMap<I::Item, (usize, u64)>,
+    // the labels by their count, the labels occurring c times are in by_count[c]
+    by_count: Vec<HashSet<I::Item>>,
+    max: usize,
+    // the most recent of the labels occurring max times, None if it has to be searched
+    most_recent: Option<I::Item>,
+    seq: u64,
+    tie: TiePolicy,
+    previous: Option<I::Item>,
+}
+
+impl<I: Iterator> RollingMajority<I> where I::Item: Hash + Eq + Clone {
+    /// This creates a new RollingMajority. Usually you should be using
+    /// [IterExt::rolling_majority](trait.IterExt.html#method.rolling_majority).
+    pub fn new(iter: I, window_size: usize, tie: TiePolicy) -> RollingMajority<I> {
+        RollingMajority {
+            iter,
+            window: Ring::new(window_size),
+            labels: HashMap::new(),
+            by_count: vec![HashSet::new()],
+            max: 0,
+            most_recent: None,
+            seq: 0,
+            tie,
+            previous: None,
+        }
+    }
+
+    fn count_of(&self, label: &I::Item) -> usize {
+        self.labels.get(label).map_or(0, |&(count, _)| count)
+    }
+
+    fn add(&mut self, label: I::Item) {
+        self.seq += 1;
+        let entry = self.labels.entry(label.clone()).or_insert((0, 0));
+        let count = entry.0;
+        *entry = (count + 1, self.seq);
+
+        self.by_count[count].remove(&label);
+        if self.by_count.len() == count + 1 {
+            self.by_count.push(HashSet::new());
+        }
+        self.by_count[count + 1].insert(label.clone());
+
+        // the new label occurred last, so it wins every tie
+        if count + 1 >= self.max {
+            self.max = count + 1;
+            self.most_recent = Some(label);
+        }
+    }
+
+    fn remove(&mut self, label: I::Item) {
+        let count = self.count_of(&label);
+        self.by_count[count].remove(&label);
+        if count == 1 {
+            self.labels.remove(&label);
+        } else {
+            self.labels.get_mut(&label).expect("evicted element is counted").0 -= 1;
+            self.by_count[count - 1].insert(label.clone());
+        }
+
+        if count == self.max && self.by_count[count].is_empty() {
+            self.max -= 1;
+        }
+        // the most common labels which occurred before it are only known by searching
+        if self.most_recent.as_ref() == Some(&label) {
+            self.most_recent = None;
+        }
+    }
+
+    fn majority(&mut self) -> I::Item {
+        if let (TiePolicy::KeepPrevious, Some(previous)) = (self.tie, self.previous.as_ref()) {
+            if self.count_of(previous) == self.max {
+                return previous.clone();
+            }
+        }
+
+        if self.most_recent.is_none() {
+            let labels = &self.labels;
+            self.most_recent = self.by_count[self.max].iter()
+                .max_by_key(|label| labels[*label].1)
+                .cloned();
+        }
+        self.most_recent.clone().expect("the window is not empty")
+    }
+}
+
+impl<I: Iterator> Iterator for RollingMajority<I> where I::Item: Hash + Eq + Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        while let Some(elt) = self.iter.next() {
+            if let Some(old) = self.window.push(elt.clone()) {
+                self.remove(old);
+            }
+            self.add(elt);
+
+            if self.window.is_full() {
+                let majority = self.majority();
+                self.previous = Some(majority.clone());
+                return Some(majority);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
@@ -0,0 +1,163 @@
+/// How missing elements (`None`) of a stream of `Option<T>` are handled.
+///
+/// See [IterExt::sliding_windows_nulls](trait.IterExt.html#method.sliding_windows_nulls) for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NullPolicy<T> {
+    /// Drop missing elements, windows only hold observed values.
+    Skip,
+    /// Replace missing elements with the most recent observed value.
+    /// Missing elements before the first observed value are dropped.
+    ForwardFill,
+    /// Replace missing elements with a constant, e.g. zero.
+    Constant(T),
+}
+
+/// Iterator replacing the missing elements of a stream of `Option<T>` according to a
+/// [NullPolicy](enum.NullPolicy.html).
+pub struct FillNulls<I, T> {
+    iter: I,
+    policy: NullPolicy<T>,
+    // the most recent observed value, only kept for NullPolicy::ForwardFill
+    last: Option<T>,
+}
+
+impl<I, T> FillNulls<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    /// This creates a new FillNulls iterator. Usually you should be using
+    /// [IterExt::sliding_windows_nulls](trait.IterExt.html#method.sliding_windows_nulls).
+    pub fn new(iter: I, policy: NullPolicy<T>) -> FillNulls<I, T> {
+        FillNulls {
+            iter,
+            policy,
+            last: None,
+        }
+    }
+}
+
+impl<I, T> Iterator for FillNulls<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for elt in &mut self.iter {
+            match (elt, &self.policy) {
+                (Some(x), NullPolicy::ForwardFill) => {
+                    self.last = Some(x.clone());
+                    return Some(x);
+                },
+                (Some(x), _) => return Some(x),
+                (None, NullPolicy::Skip) => {},
+                (None, NullPolicy::ForwardFill) => {
+                    if self.last.is_some() {
+                        return self.last.clone();
+                    }
+                },
+                (None, NullPolicy::Constant(c)) => return Some(c.clone()),
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        match self.policy {
+            NullPolicy::Constant(_) => (lower, upper),
+            NullPolicy::ForwardFill if self.last.is_some() => (lower, upper),
+            _ => (0, upper),
+        }
+    }
+}
+
+/// Iterator replacing missing elements with the most recent observed value.
+///
+/// See [IterExt::ffill](trait.IterExt.html#method.ffill) for more information.
+pub struct ForwardFill<I, T> {
+    iter: I,
+    last: Option<T>,
+}
+
+impl<I, T> ForwardFill<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    /// This creates a new ForwardFill iterator. Usually you should be using
+    /// [IterExt::ffill](trait.IterExt.html#method.ffill).
+    pub fn new(iter: I) -> ForwardFill<I, T> {
+        ForwardFill { iter, last: None }
+    }
+}
+
+impl<I, T> Iterator for ForwardFill<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    type Item = Option<T>;
+
+    fn next(&mut self) -> Option<Option<T>> {
+        match self.iter.next()? {
+            Some(x) => {
+                self.last = Some(x.clone());
+                Some(Some(x))
+            },
+            None => Some(self.last.clone()),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator replacing missing elements with the next observed value.
+///
+/// See [IterExt::bfill](trait.IterExt.html#method.bfill) for more information.
+pub struct BackwardFill<I, T> {
+    iter: I,
+    // the value ending the current run of missing elements, None for a trailing run
+    value: Option<T>,
+    // the number of elements left to yield from the current run, including the value
+    remaining: usize,
+}
+
+impl<I, T> BackwardFill<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    /// This creates a new BackwardFill iterator. Usually you should be using
+    /// [IterExt::bfill](trait.IterExt.html#method.bfill).
+    pub fn new(iter: I) -> BackwardFill<I, T> {
+        BackwardFill { iter, value: None, remaining: 0 }
+    }
+}
+
+impl<I, T> Iterator for BackwardFill<I, T> where I: Iterator<Item = Option<T>>, T: Clone {
+    type Item = Option<T>;
+
+    fn next(&mut self) -> Option<Option<T>> {
+        if self.remaining == 0 {
+            // look ahead to the end of the run of missing elements, only counting them
+            let mut missing = 0;
+            loop {
+                match self.iter.next() {
+                    Some(Some(x)) => {
+                        self.value = Some(x);
+                        self.remaining = missing + 1;
+                        break;
+                    },
+                    Some(None) => missing += 1,
+                    None => {
+                        self.value = None;
+                        self.remaining = missing;
+                        break;
+                    },
+                }
+            }
+        }
+
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            Some(self.value.take())
+        } else {
+            Some(self.value.clone())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(self.remaining), upper.and_then(|upper| upper.checked_add(self.remaining)))
+    }
+}
@@ -0,0 +1,53 @@
+use std::ops::{Add, Mul, Sub};
+
+#[cfg(feature = "num-traits")]
+use num_traits::{Num, ToPrimitive};
+
+/// The numeric types the statistics adaptors work with.
+///
+/// By default this is implemented for all primitive integer and float types. With the
+/// `num-traits` feature it is instead implemented for every type implementing
+/// `num_traits::Num` and `num_traits::ToPrimitive`, e.g. `rust_decimal::Decimal`.
+///
+/// The adaptors accumulate their statistics as `f64`, `to_f64` is used to convert elements.
+pub trait WindowNum: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    /// The additive identity.
+    fn zero() -> Self;
+
+    /// Converts `self` into a `f64`, possibly losing precision.
+    /// Values which can't be represented at all convert to `NaN`.
+    fn to_f64(self) -> f64;
+}
+
+#[cfg(not(feature = "num-traits"))]
+macro_rules! impl_window_num {
+    ($zero:expr => $($t:ty),*) => {
+        $(
+            impl WindowNum for $t {
+                fn zero() -> $t {
+                    $zero
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(not(feature = "num-traits"))]
+impl_window_num!(0 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+#[cfg(not(feature = "num-traits"))]
+impl_window_num!(0.0 => f32, f64);
+
+#[cfg(feature = "num-traits")]
+impl<T> WindowNum for T where T: Copy + PartialOrd + Num + ToPrimitive {
+    fn zero() -> T {
+        T::zero()
+    }
+
+    fn to_f64(self) -> f64 {
+        ToPrimitive::to_f64(&self).unwrap_or(f64::NAN)
+    }
+}
@@ -0,0 +1,55 @@
+use smallvec::SmallVec;
+
+use sliding_windows::{Adaptor, AsWindowStorage, Storage};
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: Clone {
+    /// Yields every window as an owned `SmallVec` in logical order, which stores windows of
+    /// up to 8 elements inline.
+    ///
+    /// Unlike `Window`s, the yielded items outlive the next call to `next()`, so they can be
+    /// fed into `itertools` combinators like `group_by` or `kmerge`.
+    ///
+    /// Only available with the `itertools` feature.
+    ///
+    /// ```
+    /// # extern crate itertools;
+    /// # extern crate sliding_windows;
+    /// use itertools::Itertools;
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// # fn main() {
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let runs: Vec<(bool, usize)> = [1, 2, 4, 3, 1, 5].iter().cloned()
+    ///     .sliding_windows(&mut storage)
+    ///     .into_owned()
+    ///     .chunk_by(|w| w[0] < w[1])
+    ///     .into_iter()
+    ///     .map(|(rising, windows)| (rising, windows.count()))
+    ///     .collect();
+    ///
+    /// assert_eq!(runs, [(true, 2), (false, 2), (true, 1)]);
+    /// # }
+    /// ```
+    pub fn into_owned(self) -> OwnedWindows<'a, I, S> {
+        OwnedWindows { inner: self }
+    }
+}
+
+/// Owned windows, see [Adaptor::into_owned](struct.Adaptor.html#method.into_owned).
+///
+/// Only available with the `itertools` feature.
+pub struct OwnedWindows<'a, I: Iterator, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a {
+    inner: Adaptor<'a, I, S>,
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Iterator for OwnedWindows<'a, I, S> where I::Item: Clone {
+    type Item = SmallVec<[I::Item; 8]>;
+
+    fn next(&mut self) -> Option<SmallVec<[I::Item; 8]>> {
+        self.inner.next().map(|window| window.iter().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
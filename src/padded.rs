@@ -0,0 +1,99 @@
+use std::iter::Fuse;
+
+use sliding_windows::{Adaptor, AsWindowStorage};
+
+/// The elements of an iterator framed by `window_size - 1` copies of a padding value on
+/// both ends, see [Adaptor::pad_with](struct.Adaptor.html#method.pad_with).
+pub struct Padded<I: Iterator> {
+    iter: Fuse<I>,
+    value: I::Item,
+    // the number of padding values on each end
+    pad: usize,
+    started: bool,
+    // the first element, read ahead to leave an empty input unpadded
+    first: Option<I::Item>,
+    leading: usize,
+    trailing: usize,
+}
+
+impl<I: Iterator> Padded<I> where I::Item: Clone {
+    fn new(iter: I, value: I::Item, pad: usize) -> Padded<I> {
+        Padded {
+            iter: iter.fuse(),
+            value,
+            pad,
+            started: false,
+            first: None,
+            leading: 0,
+            trailing: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Padded<I> where I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.started {
+            self.started = true;
+            self.first = self.iter.next();
+            if self.first.is_some() {
+                self.leading = self.pad;
+                self.trailing = self.pad;
+            }
+        }
+
+        if self.leading > 0 {
+            self.leading -= 1;
+            return Some(self.value.clone());
+        }
+        if let Some(elt) = self.first.take().or_else(|| self.iter.next()) {
+            return Some(elt);
+        }
+        if self.trailing > 0 {
+            self.trailing -= 1;
+            return Some(self.value.clone());
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if !self.started {
+            // an empty input is not padded
+            let lower = if lower > 0 { lower.saturating_add(2 * self.pad) } else { 0 };
+            return (lower, upper.and_then(|upper| upper.checked_add(2 * self.pad)));
+        }
+
+        let buffered = self.leading + self.first.is_some() as usize + self.trailing;
+        (lower.saturating_add(buffered), upper.and_then(|upper| upper.checked_add(buffered)))
+    }
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: Clone {
+    /// Pads the input with `window_size - 1` copies of `value` on both ends, so the windows
+    /// overlapping the start or the end of the input are yielded with exactly `window_size`
+    /// elements, e.g. zero padded frames for signal processing. An empty input yields no
+    /// window.
+    ///
+    /// Call this before the first window is yielded, otherwise the leading padding ends up
+    /// in the middle of the windows.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let windows: Vec<Vec<u32>> = (1..3).sliding_windows(&mut storage)
+    ///     .pad_with(0)
+    ///     .map(|window| window.iter().cloned().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(windows, [vec![0, 0, 1], vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 0]]);
+    /// ```
+    pub fn pad_with(self, value: I::Item) -> Adaptor<'a, Padded<I>, S> {
+        let pad = self.window_size().saturating_sub(1);
+        self.map_iter(|iter| Padded::new(iter, value, pad))
+    }
+}
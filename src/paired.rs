@@ -0,0 +1,58 @@
+use std::iter::Map;
+
+use sliding_windows::{Window, WindowIter};
+
+/// Iterator pairing every element with a value derived from it.
+///
+/// See [IterExt::sliding_windows_paired](trait.IterExt.html#method.sliding_windows_paired) for more information.
+pub struct Paired<I, F> {
+    iter: I,
+    derive: F,
+}
+
+impl<I: Iterator, U, F> Paired<I, F> where F: FnMut(&I::Item) -> U {
+    /// This creates a new Paired iterator. Usually you should be using
+    /// [IterExt::sliding_windows_paired](trait.IterExt.html#method.sliding_windows_paired).
+    pub fn new(iter: I, derive: F) -> Paired<I, F> {
+        Paired {
+            iter,
+            derive,
+        }
+    }
+}
+
+impl<I: Iterator, U, F> Iterator for Paired<I, F> where F: FnMut(&I::Item) -> U {
+    type Item = (I::Item, U);
+
+    fn next(&mut self) -> Option<(I::Item, U)> {
+        let elt = self.iter.next()?;
+        let derived = (self.derive)(&elt);
+        Some((elt, derived))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// The elements of a window of pairs, see [Window::originals](struct.Window.html#method.originals).
+pub type Originals<'w, T, U> = Map<WindowIter<'w, (T, U)>, fn(&(T, U)) -> &T>;
+
+/// The derived values of a window of pairs, see [Window::derived](struct.Window.html#method.derived).
+pub type Derived<'w, T, U> = Map<WindowIter<'w, (T, U)>, fn(&(T, U)) -> &U>;
+
+impl<'a, T, U> Window<'a, (T, U)> {
+    /// The original elements of a window of
+    /// [IterExt::sliding_windows_paired](trait.IterExt.html#method.sliding_windows_paired),
+    /// in logical order.
+    pub fn originals(&self) -> Originals<'_, T, U> {
+        self.iter().map(|pair| &pair.0)
+    }
+
+    /// The values derived from the elements of a window of
+    /// [IterExt::sliding_windows_paired](trait.IterExt.html#method.sliding_windows_paired),
+    /// in logical order.
+    pub fn derived(&self) -> Derived<'_, T, U> {
+        self.iter().map(|pair| &pair.1)
+    }
+}
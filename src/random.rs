@@ -0,0 +1,69 @@
+use rand::Rng;
+
+use sliding_windows::Window;
+
+impl<'a, T> Window<'a, T> {
+    /// Visits the elements of the window in a random order, e.g. for windowed stochastic
+    /// algorithms or to test that a computation doesn't depend on the order of the elements.
+    ///
+    /// The elements are not copied, only a buffer of their positions is shuffled.
+    ///
+    /// Only available with the `rand` feature.
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate sliding_windows;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// # fn main() {
+    /// let mut rng = StdRng::seed_from_u64(1);
+    /// let mut storage: Storage<u32> = Storage::new(4);
+    ///
+    /// for window in (0..10).sliding_windows(&mut storage) {
+    ///     let mut shuffled: Vec<u32> = window.iter_shuffled(&mut rng).cloned().collect();
+    ///     shuffled.sort();
+    ///     assert_eq!(window, &shuffled[..]);
+    /// }
+    /// # }
+    /// ```
+    pub fn iter_shuffled<R: Rng + ?Sized>(&self, rng: &mut R) -> WindowIterShuffled<'_, T> {
+        let (older, newer) = self.iter().as_slice_pair();
+        let mut order: Vec<usize> = (0..self.fill()).collect();
+        // Fisher-Yates
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.random_range(0..=i));
+        }
+
+        WindowIterShuffled { older, newer, order: order.into_iter() }
+    }
+}
+
+/// Iterator over the elements of a window in random order.
+///
+/// See [Window::iter_shuffled](struct.Window.html#method.iter_shuffled) for more information.
+pub struct WindowIterShuffled<'w, T: 'w> {
+    older: &'w [T],
+    newer: &'w [T],
+    // the shuffled logical positions of the elements not visited yet
+    order: ::std::vec::IntoIter<usize>,
+}
+
+impl<'w, T> Iterator for WindowIterShuffled<'w, T> {
+    type Item = &'w T;
+
+    fn next(&mut self) -> Option<&'w T> {
+        let i = self.order.next()?;
+        match self.older.get(i) {
+            Some(elt) => Some(elt),
+            None => Some(&self.newer[i - self.older.len()]),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'w, T> ExactSizeIterator for WindowIterShuffled<'w, T> {}
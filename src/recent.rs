@@ -0,0 +1,243 @@
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use ring::Ring;
+
+/// The set of distinct values among the last `window_size` pushed values.
+///
+/// Membership is answered in `O(1)` from a hash index of the window, which is kept up to
+/// date as values are evicted. This is the building block of windowed deduplication, i.e.
+/// "drop an event if it was seen in the last N events".
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::RecentSet;
+///
+/// let mut recent = RecentSet::new(3);
+/// for event in &["a", "b", "a", "c"] {
+///     recent.push(*event);
+/// }
+///
+/// // the window holds "b", "a", "c", the first "a" was evicted
+/// assert!(recent.contains(&"a"));
+/// assert_eq!(recent.len(), 3);
+///
+/// recent.push("d");
+/// assert!(!recent.contains(&"b"));
+/// ```
+pub struct RecentSet<T> {
+    window: Ring<T>,
+    // the number of occurrences of every value in the window
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq + Clone> RecentSet<T> {
+    /// Create an empty `RecentSet` over the last `window_size` values.
+    pub fn new(window_size: usize) -> RecentSet<T> {
+        RecentSet {
+            window: Ring::new(window_size),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// The number of values the set remembers.
+    pub fn window_size(&self) -> usize {
+        self.window.capacity()
+    }
+
+    /// The number of distinct values in the window.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Whether `value` is one of the last `window_size` pushed values.
+    pub fn contains(&self, value: &T) -> bool {
+        self.counts.contains_key(value)
+    }
+
+    /// Push `value` as the most recent value, evicting the oldest one if the window is full.
+    /// Returns whether `value` was already in the window before.
+    pub fn push(&mut self, value: T) -> bool {
+        // a window of size 0 remembers nothing
+        if self.window.capacity() == 0 {
+            return false;
+        }
+        let seen = self.contains(&value);
+
+        if let Some(evicted) = self.window.push(value.clone()) {
+            self.forget(&evicted);
+        }
+        *self.counts.entry(value).or_insert(0) += 1;
+
+        seen
+    }
+
+    fn forget(&mut self, value: &T) {
+        let count = self.counts.get_mut(value).expect("values in the window are counted");
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(value);
+        }
+    }
+}
+
+/// Iterator yielding the elements of the wrapped iterator which are not among the preceding
+/// `window_size` elements.
+///
+/// See [IterExt::dedup_within](trait.IterExt.html#method.dedup_within) for more information.
+pub struct DedupWithin<I: Iterator> {
+    iter: I,
+    recent: RecentSet<I::Item>,
+}
+
+impl<I: Iterator> DedupWithin<I> where I::Item: Hash + Eq + Clone {
+    /// This creates a new DedupWithin iterator. Usually you should be using
+    /// [IterExt::dedup_within](trait.IterExt.html#method.dedup_within).
+    pub fn new(iter: I, window_size: usize) -> DedupWithin<I> {
+        DedupWithin {
+            iter,
+            recent: RecentSet::new(window_size),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for DedupWithin<I> where I::Item: Hash + Eq + Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let recent = &mut self.recent;
+        self.iter.find(|elt| !recent.push(elt.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        // every element is yielded without a window, only the first one with an empty window,
+        // otherwise every remaining element may be a duplicate
+        let lower = if self.recent.window_size() == 0 {
+            lower
+        } else if self.recent.is_empty() {
+            lower.min(1)
+        } else {
+            0
+        };
+        (lower, upper)
+    }
+}
+
+struct Candidate<T> {
+    value: T,
+    // the position of the value in the pushed sequence
+    seq: u64,
+    priority: u64,
+    // the number of later values with a smaller priority
+    dominated: usize,
+}
+
+/// A uniform random sample of `k` of the last `window_size` pushed values, without
+/// replacement.
+///
+/// Every value gets a random priority and the sample consists of the `k` values of the
+/// window with the smallest priorities, so it stays uniform as values are evicted. Only
+/// values which might still become part of the sample are kept, `O(k log(window_size))` in
+/// expectation.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::RecentSample;
+///
+/// let mut sample = RecentSample::new(1000, 10).with_seed(7);
+/// for request in 0..5000 {
+///     sample.push(request);
+/// }
+///
+/// let values = sample.sample();
+/// assert_eq!(values.len(), 10);
+/// assert!(values.iter().all(|&&request| request >= 4000));
+/// ```
+pub struct RecentSample<T> {
+    window_size: usize,
+    k: usize,
+    // the values which might be part of the sample, oldest first
+    candidates: VecDeque<Candidate<T>>,
+    seq: u64,
+    rng: SplitMix64,
+}
+
+impl<T> RecentSample<T> {
+    /// Create an empty `RecentSample` of `k` of the last `window_size` values, seeded from the
+    /// randomness of the standard library.
+    pub fn new(window_size: usize, k: usize) -> RecentSample<T> {
+        let seed = RandomState::new().hash_one(0u8);
+        RecentSample {
+            window_size,
+            k,
+            candidates: VecDeque::new(),
+            seq: 0,
+            rng: SplitMix64(seed),
+        }
+    }
+
+    /// Use `seed` for the priorities, making the sample reproducible.
+    pub fn with_seed(mut self, seed: u64) -> RecentSample<T> {
+        self.rng = SplitMix64(seed);
+        self
+    }
+
+    /// Push `value` as the most recent value, evicting the oldest one if the window is full.
+    pub fn push(&mut self, value: T) {
+        if self.window_size == 0 || self.k == 0 {
+            return;
+        }
+
+        let priority = self.rng.next_u64();
+        let k = self.k;
+        self.candidates.retain_mut(|candidate| {
+            candidate.dominated += (candidate.priority > priority) as usize;
+            candidate.dominated < k
+        });
+        self.candidates.push_back(Candidate { value, seq: self.seq, priority, dominated: 0 });
+        self.seq += 1;
+
+        let window_start = self.seq.saturating_sub(self.window_size as u64);
+        while self.candidates.front().is_some_and(|candidate| candidate.seq < window_start) {
+            self.candidates.pop_front();
+        }
+    }
+
+    /// The sampled values, oldest first. There are less than `k` if less than `k` values
+    /// were pushed.
+    pub fn sample(&self) -> Vec<&T> {
+        let mut priorities: Vec<u64> = self.candidates.iter().map(|candidate| candidate.priority).collect();
+        priorities.sort_unstable();
+        let threshold = match priorities.get(self.k.saturating_sub(1)) {
+            Some(&threshold) => threshold,
+            None => u64::MAX,
+        };
+
+        self.candidates.iter()
+            .filter(|candidate| candidate.priority <= threshold)
+            .map(|candidate| &candidate.value)
+            .take(self.k)
+            .collect()
+    }
+}
+
+// a small, fast pseudo random generator, good enough for sampling
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::slice;
+use std::str::FromStr;
+
+use sliding_windows::{Adaptor, AsWindowStorage, Storage, Window};
+
+/// The elements pushed into an [Adaptor](struct.Adaptor.html) and the boundaries of the windows
+/// it yielded, recorded by [Adaptor::record](struct.Adaptor.html#method.record).
+///
+/// A window always holds the last elements pushed before it was yielded, so the trace stores
+/// every element once and two numbers per window. Mutations by
+/// [with_decay](struct.Adaptor.html#method.with_decay) are not recorded, replayed windows hold
+/// the elements as they were pushed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<T> {
+    elements: Vec<T>,
+    // the number of elements pushed before every window and the fill of the window
+    windows: Vec<(usize, usize)>,
+}
+
+impl<T> Trace<T> {
+    fn new() -> Trace<T> {
+        Trace {
+            elements: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// All elements pushed, in order.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// The number of recorded windows.
+    pub fn num_windows(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// The elements of the `i`th recorded window, oldest first.
+    pub fn window(&self, i: usize) -> Option<&[T]> {
+        let (end, fill) = *self.windows.get(i)?;
+        Some(&self.elements[end - fill..end])
+    }
+
+    /// Yields the recorded windows again as `Window`s of `storage`, so the code which consumed
+    /// the original windows can be run on them. The window size of `storage` is changed to the
+    /// fill of the largest window.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut recorder = (0..6).sliding_windows(&mut storage).emit_every(2).record();
+    /// let sums: Vec<u32> = recorder.by_ref().map(|w| w.iter().sum()).collect();
+    /// let trace = recorder.into_trace();
+    ///
+    /// let mut replay_storage = Storage::new(0);
+    /// let replayed: Vec<u32> = trace.replay(&mut replay_storage).map(|w| w.iter().sum()).collect();
+    /// assert_eq!(replayed, sums);
+    /// assert_eq!(replayed, [3, 9]);
+    /// ```
+    pub fn replay<'t, 's>(&'t self, storage: &'s mut Storage<T>) -> Replay<'t, 's, T> where T: Clone {
+        let window_size = self.windows.iter().map(|&(_, fill)| fill).max().unwrap_or(0);
+        storage.set_window_size(window_size);
+
+        Replay {
+            trace: self,
+            windows: self.windows.iter(),
+            storage,
+        }
+    }
+
+    /// Writes the trace as text, one line per event in the order they happened: `+ <element>`
+    /// for a pushed element and `= <fill>` for a yielded window. Elements must display on a
+    /// single line for [read_from](#method.read_from) to read them back.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> where T: Display {
+        let mut elements = self.elements.iter();
+        let mut pushed = 0;
+        for &(end, fill) in &self.windows {
+            for elt in elements.by_ref().take(end - pushed) {
+                writeln!(writer, "+ {}", elt)?;
+            }
+            pushed = end;
+            writeln!(writer, "= {}", fill)?;
+        }
+        for elt in elements {
+            writeln!(writer, "+ {}", elt)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a trace written by [write_to](#method.write_to).
+    ///
+    /// # Errors:
+    ///
+    /// Returns an error of kind `InvalidData` for lines which are no valid event.
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Trace<T>> where T: FromStr {
+        let mut trace = Trace::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid event on line {}: {:?}", i + 1, line));
+
+            if let Some(elt) = line.strip_prefix("+ ") {
+                trace.elements.push(elt.parse().map_err(|_| invalid())?);
+            } else if let Some(fill) = line.strip_prefix("= ") {
+                let fill: usize = fill.parse().map_err(|_| invalid())?;
+                if fill > trace.elements.len() {
+                    return Err(invalid());
+                }
+                trace.windows.push((trace.elements.len(), fill));
+            } else if !line.is_empty() {
+                return Err(invalid());
+            }
+        }
+        Ok(trace)
+    }
+}
+
+// passes the elements of `iter` on and keeps a clone of each in `trace`
+struct Tap<I: Iterator> {
+    iter: I,
+    trace: Trace<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Tap<I> where I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let elt = self.iter.next()?;
+        self.trace.elements.push(elt.clone());
+        Some(elt)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: Clone {
+    /// Records every pushed element and the boundaries of every yielded window into a
+    /// [Trace](struct.Trace.html), which reproduces the exact sequence of windows later, e.g.
+    /// to debug a pipeline on the data it failed on in production.
+    ///
+    /// The returned `Recorder` yields the same windows as this adaptor. If the adaptor already
+    /// yielded windows or was warmed up, the trace starts with the buffered elements.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if a `Window` of this adaptor is still alive.
+    pub fn record(self) -> Recorder<'a, I, S> {
+        let mut trace = Trace::new();
+        trace.elements = self.buffered();
+        Recorder {
+            inner: self.map_iter(|iter| Tap { iter, trace }),
+        }
+    }
+}
+
+/// Windows of an adaptor being recorded, see [Adaptor::record](struct.Adaptor.html#method.record).
+pub struct Recorder<'a, I: Iterator, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a + Clone {
+    inner: Adaptor<'a, Tap<I>, S>,
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Recorder<'a, I, S> where I::Item: Clone {
+    /// The trace recorded so far.
+    pub fn trace(&self) -> &Trace<I::Item> {
+        &self.inner.source().trace
+    }
+
+    /// Stops recording and returns the trace.
+    pub fn into_trace(self) -> Trace<I::Item> {
+        self.inner.into_source().trace
+    }
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Iterator for Recorder<'a, I, S> where I::Item: Clone {
+    type Item = Window<'a, I::Item>;
+
+    fn next(&mut self) -> Option<Window<'a, I::Item>> {
+        let window = self.inner.next()?;
+        let trace = &mut self.inner.source_mut().trace;
+        trace.windows.push((trace.elements.len(), window.fill()));
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Recorded windows yielded again, see [Trace::replay](struct.Trace.html#method.replay).
+pub struct Replay<'t, 's, T: 't + 's> {
+    trace: &'t Trace<T>,
+    windows: slice::Iter<'t, (usize, usize)>,
+    storage: &'s Storage<T>,
+}
+
+impl<'t, 's, T: Clone> Iterator for Replay<'t, 's, T> {
+    type Item = Window<'s, T>;
+
+    fn next(&mut self) -> Option<Window<'s, T>> {
+        let &(end, fill) = self.windows.next()?;
+        self.storage.refill(&self.trace.elements[end - fill..end]);
+        Some(self.storage.new_window())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<'t, 's, T: Clone> ExactSizeIterator for Replay<'t, 's, T> {}
@@ -0,0 +1,99 @@
+use num::WindowNum;
+
+/// The interpolation used by `Resample`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear interpolation between the two neighbouring samples.
+    Linear,
+    /// Catmull-Rom spline through the four neighbouring samples.
+    Cubic,
+}
+
+/// Iterator yielding the wrapped iterator's samples interpolated at a different rate.
+///
+/// See [IterExt::resample](trait.IterExt.html#method.resample) for more information.
+pub struct Resample<I> {
+    iter: I,
+    interpolation: Interpolation,
+    // distance between two output samples in input samples
+    step: f64,
+    // index of the next output sample
+    index: u64,
+    // the last four input samples, samples[3] being the newest
+    samples: [f64; 4],
+    // input index of samples[3], -1 before the first sample
+    newest: i64,
+    exhausted: bool,
+}
+
+impl<I: Iterator> Resample<I> where I::Item: WindowNum {
+    /// This creates a new Resample iterator. Usually you should be using
+    /// [IterExt::resample](trait.IterExt.html#method.resample).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if one of the rates is not positive.
+    pub fn new(iter: I, src_rate: f64, dst_rate: f64, interpolation: Interpolation) -> Resample<I> {
+        assert!(src_rate > 0.0 && dst_rate > 0.0, "sample rates must be positive");
+
+        Resample {
+            iter,
+            interpolation,
+            step: src_rate / dst_rate,
+            index: 0,
+            samples: [0.0; 4],
+            newest: -1,
+            exhausted: false,
+        }
+    }
+
+    // the input sample at index i, indices outside of the input are clamped to its ends
+    fn sample(&self, i: i64) -> f64 {
+        let i = i.max(0).min(self.newest);
+        self.samples[(3 - (self.newest - i)) as usize]
+    }
+}
+
+impl<I: Iterator> Iterator for Resample<I> where I::Item: WindowNum {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let position = self.index as f64 * self.step;
+        let i = position.floor() as i64;
+        let t = position - i as f64;
+
+        // pull input until all samples the interpolation looks at are known
+        let needed = match self.interpolation {
+            Interpolation::Linear => i + 1,
+            Interpolation::Cubic => i + 2,
+        };
+        while self.newest < needed && !self.exhausted {
+            match self.iter.next() {
+                Some(x) => {
+                    self.samples = [self.samples[1], self.samples[2], self.samples[3], x.to_f64()];
+                    self.newest += 1;
+                },
+                None => self.exhausted = true,
+            }
+        }
+
+        if position > self.newest as f64 {
+            return None;
+        }
+
+        let (p1, p2) = (self.sample(i), self.sample(i + 1));
+        let value = match self.interpolation {
+            Interpolation::Linear => p1 + (p2 - p1) * t,
+            Interpolation::Cubic => {
+                let (p0, p3) = (self.sample(i - 1), self.sample(i + 2));
+                0.5 * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+                    + (3.0 * (p1 - p2) + p3 - p0) * t * t * t)
+            },
+        };
+
+        self.index += 1;
+        Some(value)
+    }
+}
@@ -0,0 +1,70 @@
+use std::iter::Chain;
+use std::slice;
+
+use sliding_windows::windows_len;
+
+/// A fixed capacity ring buffer which hands back the element it evicts.
+///
+/// This is the backing buffer of the adaptors which yield owned values computed
+/// from the trailing window (statistics, scores, ...) instead of `Window`s.
+pub struct Ring<T> {
+    capacity: usize,
+    // index of the oldest element, only meaningful once the ring is full
+    offset: usize,
+    data: Vec<T>,
+}
+
+// only exposed to benchmarks, which don't need is_empty
+#[allow(clippy::len_without_is_empty)]
+impl<T> Ring<T> {
+    pub fn new(capacity: usize) -> Ring<T> {
+        Ring {
+            capacity,
+            offset: 0,
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.data.len() == self.capacity
+    }
+
+    // push value onto self, return the evicted element if the ring was full
+    // a ring with capacity 0 evicts every element immediately
+    pub fn push(&mut self, elt: T) -> Option<T> {
+        if self.capacity == 0 {
+            return Some(elt);
+        }
+
+        if self.data.len() < self.capacity {
+            self.data.push(elt);
+            return None;
+        }
+
+        let evicted = ::std::mem::replace(&mut self.data[self.offset], elt);
+        self.offset = if self.offset + 1 == self.capacity { 0 } else { self.offset + 1 };
+        Some(evicted)
+    }
+
+    // the number of full windows left, given the size hint of the remaining elements
+    pub fn windows_hint(&self, (lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+        // between two windows the ring is empty or full, the window over a full ring was yielded
+        let buffered = self.data.len().saturating_sub(1);
+        let windows = |len: usize| windows_len(len.saturating_add(buffered), self.capacity, 1);
+        (windows(lower), upper.map(windows))
+    }
+
+    // iterate from the oldest to the newest element
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (newer, older) = self.data.split_at(self.offset);
+        older.iter().chain(newer.iter())
+    }
+}
@@ -0,0 +1,639 @@
+use std::iter::Zip;
+
+use num::WindowNum;
+use ring::Ring;
+
+/// Mean and sample variance of the trailing window, maintained incrementally.
+///
+/// This is the building block of the adaptors which need to know the spread of the
+/// trailing window, e.g. the change-point detectors. The mean and the sum of squared
+/// deviations from it are updated like Welford's algorithm, so values with a large offset
+/// don't lose their spread to cancellation.
+pub struct Moments {
+    window: Ring<f64>,
+    mean: f64,
+    // sum of the squared deviations from the mean
+    m2: f64,
+}
+
+impl Moments {
+    pub fn new(window_size: usize) -> Moments {
+        Moments {
+            window: Ring::new(window_size),
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn push(&mut self, x: f64) {
+        if self.window.capacity() == 0 {
+            return;
+        }
+
+        match self.window.push(x) {
+            None => {
+                let delta = x - self.mean;
+                self.mean += delta / self.window.len() as f64;
+                self.m2 += delta * (x - self.mean);
+            },
+            Some(old) => {
+                // replace old by x, the number of elements stays the same
+                let old_mean = self.mean;
+                self.mean += (x - old) / self.window.len() as f64;
+                self.m2 = (self.m2 + (x - old) * (x - self.mean + old - old_mean)).max(0.0);
+            },
+        }
+    }
+
+    // NaN for an empty window
+    pub fn mean(&self) -> f64 {
+        if self.window.len() == 0 { f64::NAN } else { self.mean }
+    }
+
+    // sample variance, NaN for less than two elements
+    pub fn variance(&self) -> f64 {
+        let n = self.window.len();
+        if n < 2 { f64::NAN } else { self.m2 / (n - 1) as f64 }
+    }
+}
+
+// running means and centred co-moments of the pairs currently in the window, updated like
+// Welford's algorithm so values with a large common offset don't cancel
+#[derive(Default)]
+struct PairedMoments {
+    n: f64,
+    mean_x: f64,
+    mean_y: f64,
+    // sums of (x - mean_x)^2, (y - mean_y)^2 and (x - mean_x) * (y - mean_y)
+    m2_x: f64,
+    m2_y: f64,
+    c_xy: f64,
+}
+
+impl PairedMoments {
+    fn add(&mut self, x: f64, y: f64) {
+        self.n += 1.0;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx / self.n;
+        self.mean_y += dy / self.n;
+        self.m2_x += dx * (x - self.mean_x);
+        self.m2_y += dy * (y - self.mean_y);
+        self.c_xy += dx * (y - self.mean_y);
+    }
+
+    fn sub(&mut self, x: f64, y: f64) {
+        if self.n <= 1.0 {
+            *self = PairedMoments::default();
+            return;
+        }
+
+        // the inverse of add: the means without the pair, then its contribution
+        self.n -= 1.0;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x -= dx / self.n;
+        self.mean_y -= dy / self.n;
+        self.m2_x = (self.m2_x - dx * (x - self.mean_x)).max(0.0);
+        self.m2_y = (self.m2_y - dy * (y - self.mean_y)).max(0.0);
+        self.c_xy -= (x - self.mean_x) * dy;
+    }
+
+    // sample covariance (n - 1 in the denominator)
+    fn cov(&self) -> f64 {
+        self.c_xy / (self.n - 1.0)
+    }
+
+    // pearson correlation, NaN if either side is constant
+    fn corr(&self) -> f64 {
+        self.c_xy / (self.m2_x * self.m2_y).sqrt()
+    }
+}
+
+// a window over two iterators consumed in lockstep
+struct PairedWindow<I, J> {
+    iter: Zip<I, J>,
+    window: Ring<(f64, f64)>,
+    moments: PairedMoments,
+}
+
+impl<I, J> PairedWindow<I, J>
+    where I: Iterator, J: Iterator, I::Item: WindowNum, J::Item: WindowNum
+{
+    fn new(a: I, b: J, window_size: usize) -> PairedWindow<I, J> {
+        PairedWindow {
+            iter: a.zip(b),
+            window: Ring::new(window_size),
+            moments: PairedMoments::default(),
+        }
+    }
+
+    // slide the window, return the moments once it is full
+    fn next_full(&mut self) -> Option<&PairedMoments> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for (x, y) in &mut self.iter {
+            let (x, y) = (x.to_f64(), y.to_f64());
+            self.moments.add(x, y);
+            if let Some((old_x, old_y)) = self.window.push((x, y)) {
+                self.moments.sub(old_x, old_y);
+            }
+
+            if self.window.is_full() {
+                return Some(&self.moments);
+            }
+        }
+
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// Iterator yielding the sample covariance of the trailing window of two streams.
+///
+/// See [IterExt::rolling_cov](trait.IterExt.html#method.rolling_cov) for more information.
+pub struct RollingCov<I, J> {
+    inner: PairedWindow<I, J>,
+}
+
+impl<I, J> RollingCov<I, J>
+    where I: Iterator, J: Iterator, I::Item: WindowNum, J::Item: WindowNum
+{
+    /// This creates a new RollingCov. Usually you should be using
+    /// [IterExt::rolling_cov](trait.IterExt.html#method.rolling_cov).
+    pub fn new(a: I, b: J, window_size: usize) -> RollingCov<I, J> {
+        RollingCov { inner: PairedWindow::new(a, b, window_size) }
+    }
+}
+
+impl<I, J> Iterator for RollingCov<I, J>
+    where I: Iterator, J: Iterator, I::Item: WindowNum, J::Item: WindowNum
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        self.inner.next_full().map(PairedMoments::cov)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator yielding the pearson correlation of the trailing window of two streams.
+///
+/// See [IterExt::rolling_corr](trait.IterExt.html#method.rolling_corr) for more information.
+pub struct RollingCorr<I, J> {
+    inner: PairedWindow<I, J>,
+}
+
+impl<I, J> RollingCorr<I, J>
+    where I: Iterator, J: Iterator, I::Item: WindowNum, J::Item: WindowNum
+{
+    /// This creates a new RollingCorr. Usually you should be using
+    /// [IterExt::rolling_corr](trait.IterExt.html#method.rolling_corr).
+    pub fn new(a: I, b: J, window_size: usize) -> RollingCorr<I, J> {
+        RollingCorr { inner: PairedWindow::new(a, b, window_size) }
+    }
+}
+
+impl<I, J> Iterator for RollingCorr<I, J>
+    where I: Iterator, J: Iterator, I::Item: WindowNum, J::Item: WindowNum
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        self.inner.next_full().map(PairedMoments::corr)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// The least-squares line fitted through the elements of a window against their index.
+///
+/// Index `0` is the oldest element of the window, so `intercept` is the value of the fitted
+/// line at the oldest element and `slope` is the change per element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trend {
+    pub slope: f64,
+    pub intercept: f64,
+    /// The coefficient of determination, `NaN` if the window is constant.
+    pub r_squared: f64,
+}
+
+/// Iterator yielding the least-squares `Trend` of the trailing window.
+///
+/// See [IterExt::rolling_trend](trait.IterExt.html#method.rolling_trend) for more information.
+pub struct RollingTrend<I> {
+    iter: I,
+    window: Ring<f64>,
+    // the mean of y, the sum of (y - mean)^2 and the sum of (i - mean of i) * (y - mean),
+    // where i is the index inside of the window
+    mean: f64,
+    m2: f64,
+    sxy: f64,
+    // slides since the moments were last computed from the window
+    slides: usize,
+}
+
+impl<I: Iterator> RollingTrend<I> where I::Item: WindowNum {
+    /// This creates a new RollingTrend. Usually you should be using
+    /// [IterExt::rolling_trend](trait.IterExt.html#method.rolling_trend).
+    pub fn new(iter: I, window_size: usize) -> RollingTrend<I> {
+        RollingTrend {
+            iter,
+            window: Ring::new(window_size),
+            mean: 0.0,
+            m2: 0.0,
+            sxy: 0.0,
+            slides: 0,
+        }
+    }
+
+    // the mean of the indices 0..n
+    fn mean_x(&self) -> f64 {
+        (self.window.capacity() as f64 - 1.0) / 2.0
+    }
+
+    // compute the moments of the full window from scratch, which discards the rounding
+    // errors the updates accumulated
+    fn recompute(&mut self) {
+        let n = self.window.capacity() as f64;
+        let mean_x = self.mean_x();
+        let mean = self.window.iter().sum::<f64>() / n;
+        self.mean = mean;
+        self.m2 = self.window.iter().map(|y| (y - mean) * (y - mean)).sum();
+        self.sxy = self.window.iter().enumerate().map(|(i, y)| (i as f64 - mean_x) * (y - mean)).sum();
+        self.slides = 0;
+    }
+
+    // replace the oldest element `old` by `y` and move every other element one index down
+    fn slide(&mut self, old: f64, y: f64) {
+        let n = self.window.capacity() as f64;
+        let mean_x = self.mean_x();
+        let old_mean = self.mean;
+
+        // the deviations of the indices sum to 0, so y may be centred by any constant
+        self.sxy += (mean_x + 1.0) * (old - old_mean) + (n - 1.0 - mean_x) * (y - old_mean);
+        self.mean += (y - old) / n;
+        self.m2 = (self.m2 + (y - old) * (y - self.mean + old - old_mean)).max(0.0);
+        self.slides += 1;
+    }
+
+    fn trend(&self) -> Trend {
+        let n = self.window.capacity() as f64;
+        // the sum of (i - mean of i)^2 over the indices 0..n is constant
+        let var_x = n * (n * n - 1.0) / 12.0;
+        let slope = self.sxy / var_x;
+
+        Trend {
+            slope,
+            intercept: self.mean - slope * self.mean_x(),
+            r_squared: self.sxy * self.sxy / (var_x * self.m2),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingTrend<I> where I::Item: WindowNum {
+    type Item = Trend;
+
+    fn next(&mut self) -> Option<Trend> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        while let Some(y) = self.iter.next() {
+            let y = y.to_f64();
+            match self.window.push(y) {
+                Some(old) if self.slides + 1 < self.window.capacity() => self.slide(old, y),
+                // the window just became full or slid by a whole window
+                _ if self.window.is_full() => self.recompute(),
+                _ => continue,
+            }
+            return Some(self.trend());
+        }
+
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// How `RollingSum` and `RollingMean` update their sum when the window slides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Summation {
+    /// Add the new and subtract the evicted element. Rounding errors of every update
+    /// accumulate, which makes the sum drift over long floating-point streams.
+    Naive,
+    /// Neumaier's compensated summation, which tracks the rounding error of every update.
+    Compensated,
+}
+
+// a sum updated according to a Summation
+struct Accumulator {
+    summation: Summation,
+    sum: f64,
+    // accumulated rounding error, only used by Summation::Compensated
+    compensation: f64,
+}
+
+impl Accumulator {
+    fn new(summation: Summation) -> Accumulator {
+        Accumulator { summation, sum: 0.0, compensation: 0.0 }
+    }
+
+    fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.summation == Summation::Compensated {
+            if self.sum.abs() >= x.abs() {
+                self.compensation += (self.sum - t) + x;
+            } else {
+                self.compensation += (x - t) + self.sum;
+            }
+        }
+        self.sum = t;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.compensation = 0.0;
+    }
+}
+
+/// Iterator yielding the sum of the trailing window.
+///
+/// See [IterExt::rolling_sum](trait.IterExt.html#method.rolling_sum) for more information.
+pub struct RollingSum<I> {
+    iter: I,
+    window: Ring<f64>,
+    acc: Accumulator,
+    // recompute the sum from scratch after this many slides, 0 means never
+    recompute_every: usize,
+    slides: usize,
+}
+
+impl<I: Iterator> RollingSum<I> where I::Item: WindowNum {
+    /// This creates a new RollingSum. Usually you should be using
+    /// [IterExt::rolling_sum](trait.IterExt.html#method.rolling_sum).
+    pub fn new(iter: I, window_size: usize) -> RollingSum<I> {
+        RollingSum {
+            iter,
+            window: Ring::new(window_size),
+            acc: Accumulator::new(Summation::Naive),
+            recompute_every: 0,
+            slides: 0,
+        }
+    }
+
+    /// Selects how the sum is updated, the default is `Summation::Naive`.
+    pub fn summation(mut self, summation: Summation) -> RollingSum<I> {
+        self.acc.summation = summation;
+        self
+    }
+
+    /// Recomputes the sum from the elements of the window after every `k` slides, which bounds
+    /// the accumulated error at the cost of an O(window_size) step. `0` disables recomputation.
+    pub fn recompute_every(mut self, k: usize) -> RollingSum<I> {
+        self.recompute_every = k;
+        self
+    }
+
+    fn next_sum(&mut self) -> Option<f64> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for x in &mut self.iter {
+            let x = x.to_f64();
+            self.acc.add(x);
+
+            if let Some(old) = self.window.push(x) {
+                self.slides += 1;
+                if self.slides == self.recompute_every {
+                    self.slides = 0;
+                    self.acc.reset();
+                    for &x in self.window.iter() {
+                        self.acc.add(x);
+                    }
+                } else {
+                    self.acc.add(-old);
+                }
+            }
+
+            if self.window.is_full() {
+                return Some(self.acc.value());
+            }
+        }
+
+        None
+    }
+}
+
+impl<I: Iterator> Iterator for RollingSum<I> where I::Item: WindowNum {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        self.next_sum()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// Iterator yielding the mean of the trailing window.
+///
+/// See [IterExt::rolling_mean](trait.IterExt.html#method.rolling_mean) for more information.
+pub struct RollingMean<I> {
+    sum: RollingSum<I>,
+}
+
+impl<I: Iterator> RollingMean<I> where I::Item: WindowNum {
+    /// This creates a new RollingMean. Usually you should be using
+    /// [IterExt::rolling_mean](trait.IterExt.html#method.rolling_mean).
+    pub fn new(iter: I, window_size: usize) -> RollingMean<I> {
+        RollingMean { sum: RollingSum::new(iter, window_size) }
+    }
+
+    /// Selects how the underlying sum is updated, the default is `Summation::Naive`.
+    pub fn summation(self, summation: Summation) -> RollingMean<I> {
+        RollingMean { sum: self.sum.summation(summation) }
+    }
+
+    /// Recomputes the underlying sum after every `k` slides,
+    /// see [RollingSum::recompute_every](struct.RollingSum.html#method.recompute_every).
+    pub fn recompute_every(self, k: usize) -> RollingMean<I> {
+        RollingMean { sum: self.sum.recompute_every(k) }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingMean<I> where I::Item: WindowNum {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let n = self.sum.window.capacity() as f64;
+        self.sum.next_sum().map(|sum| sum / n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sum.size_hint()
+    }
+}
+
+// a rolling sum over the non-NaN elements of the window
+struct NanSum<I> {
+    iter: I,
+    window: Ring<f64>,
+    acc: Accumulator,
+    // number of non-NaN elements in the window
+    valid: usize,
+    min_periods: usize,
+}
+
+impl<I: Iterator> NanSum<I> where I::Item: WindowNum {
+    fn new(iter: I, window_size: usize) -> NanSum<I> {
+        NanSum {
+            iter,
+            window: Ring::new(window_size),
+            acc: Accumulator::new(Summation::Naive),
+            valid: 0,
+            min_periods: 1.min(window_size),
+        }
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        assert!(min_periods <= self.window.capacity(),
+                "min_periods {} must not exceed the window size {}", min_periods, self.window.capacity());
+        self.min_periods = min_periods;
+    }
+
+    // yields the sum and the number of non-NaN elements for every full window,
+    // the sum is None if less than min_periods elements are valid
+    fn next_sum(&mut self) -> Option<(Option<f64>, usize)> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for x in &mut self.iter {
+            let x = x.to_f64();
+            if !x.is_nan() {
+                self.acc.add(x);
+                self.valid += 1;
+            }
+
+            if let Some(old) = self.window.push(x) {
+                if !old.is_nan() {
+                    self.acc.add(-old);
+                    self.valid -= 1;
+                }
+            }
+
+            if self.window.is_full() {
+                if self.valid == 0 {
+                    // avoid yielding leftover rounding errors
+                    self.acc.reset();
+                }
+                let sum = if self.valid >= self.min_periods { Some(self.acc.value()) } else { None };
+                return Some((sum, self.valid));
+            }
+        }
+
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
+
+/// Iterator yielding the sum of the non-NaN elements of the trailing window.
+///
+/// See [IterExt::rolling_sum_skipnan](trait.IterExt.html#method.rolling_sum_skipnan) for more information.
+pub struct RollingSumSkipNan<I> {
+    sum: NanSum<I>,
+}
+
+impl<I: Iterator> RollingSumSkipNan<I> where I::Item: WindowNum {
+    /// This creates a new RollingSumSkipNan. Usually you should be using
+    /// [IterExt::rolling_sum_skipnan](trait.IterExt.html#method.rolling_sum_skipnan).
+    pub fn new(iter: I, window_size: usize) -> RollingSumSkipNan<I> {
+        RollingSumSkipNan { sum: NanSum::new(iter, window_size) }
+    }
+
+    /// Yield `None` for windows with less than `min_periods` non-NaN elements, the default is 1.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `min_periods` exceeds the window size.
+    pub fn min_periods(mut self, min_periods: usize) -> RollingSumSkipNan<I> {
+        self.sum.set_min_periods(min_periods);
+        self
+    }
+}
+
+impl<I: Iterator> Iterator for RollingSumSkipNan<I> where I::Item: WindowNum {
+    type Item = Option<f64>;
+
+    fn next(&mut self) -> Option<Option<f64>> {
+        self.sum.next_sum().map(|(sum, _)| sum)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sum.size_hint()
+    }
+}
+
+/// Iterator yielding the mean of the non-NaN elements of the trailing window.
+///
+/// See [IterExt::rolling_mean_skipnan](trait.IterExt.html#method.rolling_mean_skipnan) for more information.
+pub struct RollingMeanSkipNan<I> {
+    sum: NanSum<I>,
+}
+
+impl<I: Iterator> RollingMeanSkipNan<I> where I::Item: WindowNum {
+    /// This creates a new RollingMeanSkipNan. Usually you should be using
+    /// [IterExt::rolling_mean_skipnan](trait.IterExt.html#method.rolling_mean_skipnan).
+    pub fn new(iter: I, window_size: usize) -> RollingMeanSkipNan<I> {
+        RollingMeanSkipNan { sum: NanSum::new(iter, window_size) }
+    }
+
+    /// Yield `None` for windows with less than `min_periods` non-NaN elements, the default is 1.
+    /// Windows without any non-NaN element always yield `None`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `min_periods` exceeds the window size.
+    pub fn min_periods(mut self, min_periods: usize) -> RollingMeanSkipNan<I> {
+        self.sum.set_min_periods(min_periods);
+        self
+    }
+}
+
+impl<I: Iterator> Iterator for RollingMeanSkipNan<I> where I::Item: WindowNum {
+    type Item = Option<f64>;
+
+    fn next(&mut self) -> Option<Option<f64>> {
+        self.sum.next_sum().map(|(sum, valid)| match sum {
+            Some(sum) if valid > 0 => Some(sum / valid as f64),
+            _ => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sum.size_hint()
+    }
+}
@@ -0,0 +1,70 @@
+// the equivalence of `IterExt::runs`
+pub(crate) type EqFn<T> = fn(&T, &T) -> bool;
+
+/// A maximal run of equivalent consecutive elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run<T> {
+    /// The first element of the run.
+    pub value: T,
+    /// The position of the first element in the input.
+    pub start: usize,
+    /// The number of elements in the run, at least 1.
+    pub len: usize,
+}
+
+/// Iterator yielding the maximal runs of equivalent elements of the wrapped iterator.
+///
+/// See [IterExt::runs_by](trait.IterExt.html#method.runs_by) for more information.
+pub struct Runs<I: Iterator, F> {
+    iter: I,
+    same: F,
+    // the first element of the next run, already pulled from `iter`
+    pending: Option<I::Item>,
+    // the position of `pending` in the input
+    index: usize,
+}
+
+impl<I: Iterator, F> Runs<I, F> where F: FnMut(&I::Item, &I::Item) -> bool {
+    /// This creates a new Runs iterator. Usually you should be using
+    /// [IterExt::runs](trait.IterExt.html#method.runs) or
+    /// [IterExt::runs_by](trait.IterExt.html#method.runs_by).
+    pub fn new(iter: I, same: F) -> Runs<I, F> {
+        Runs {
+            iter,
+            same,
+            pending: None,
+            index: 0,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for Runs<I, F> where F: FnMut(&I::Item, &I::Item) -> bool {
+    type Item = Run<I::Item>;
+
+    fn next(&mut self) -> Option<Run<I::Item>> {
+        let value = match self.pending.take() {
+            Some(value) => value,
+            None => self.iter.next()?,
+        };
+        let start = self.index;
+        let mut len = 1;
+
+        for elt in &mut self.iter {
+            if (self.same)(&value, &elt) {
+                len += 1;
+            } else {
+                self.pending = Some(elt);
+                break;
+            }
+        }
+
+        self.index += len;
+        Some(Run { value, start, len })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.pending.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending).min(1), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
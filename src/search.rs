@@ -0,0 +1,69 @@
+use ring::Ring;
+
+// an odd multiplier, so the hash is a polynomial over the integers modulo 2^64
+const HASH_BASE: u64 = 0x100_0000_01b3;
+
+/// Iterator yielding the offsets into a byte stream where a needle starts.
+///
+/// See [IterExt::find_subsequence](trait.IterExt.html#method.find_subsequence) for more information.
+pub struct FindSubsequence<I> {
+    iter: I,
+    needle: Vec<u8>,
+    needle_hash: u64,
+    window: Ring<u8>,
+    window_hash: u64,
+    // HASH_BASE to the power of the needle length, the weight of an evicted byte
+    evict_factor: u64,
+    // the offset of the next window
+    offset: usize,
+}
+
+impl<I: Iterator<Item = u8>> FindSubsequence<I> {
+    /// This creates a new FindSubsequence iterator. Usually you should be using
+    /// [IterExt::find_subsequence](trait.IterExt.html#method.find_subsequence).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `needle` is empty.
+    pub fn new(iter: I, needle: &[u8]) -> FindSubsequence<I> {
+        assert!(!needle.is_empty(), "needle must not be empty");
+        FindSubsequence {
+            iter,
+            needle: needle.to_vec(),
+            needle_hash: needle.iter().fold(0, |hash, &byte| roll(hash, byte)),
+            window: Ring::new(needle.len()),
+            window_hash: 0,
+            evict_factor: needle.iter().fold(1, |factor, _| factor.wrapping_mul(HASH_BASE)),
+            offset: 0,
+        }
+    }
+}
+
+// appends `byte` to the polynomial hash
+fn roll(hash: u64, byte: u8) -> u64 {
+    hash.wrapping_mul(HASH_BASE).wrapping_add(byte as u64)
+}
+
+impl<I: Iterator<Item = u8>> Iterator for FindSubsequence<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for byte in &mut self.iter {
+            self.window_hash = roll(self.window_hash, byte);
+            if let Some(evicted) = self.window.push(byte) {
+                self.window_hash = self.window_hash.wrapping_sub((evicted as u64).wrapping_mul(self.evict_factor));
+                self.offset += 1;
+            }
+
+            // equal hashes are only a candidate, the window is compared to rule out collisions
+            if self.window.is_full() && self.window_hash == self.needle_hash && self.window.iter().eq(&self.needle) {
+                return Some(self.offset);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.window.windows_hint(self.iter.size_hint()).1)
+    }
+}
@@ -0,0 +1,75 @@
+use std::ops::Sub;
+
+/// A pair of consecutive timestamps flagged by
+/// [IterExt::timestamp_skew](trait.IterExt.html#method.timestamp_skew).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skew<D> {
+    /// The timestamp at `index` is `delta` before the previous timestamp.
+    Backwards { index: usize, delta: D },
+    /// The timestamp at `index` is `delta` after the previous timestamp, more than the bound.
+    Jump { index: usize, delta: D },
+}
+
+impl<D> Skew<D> {
+    /// The position of the later timestamp of the pair in the input.
+    pub fn index(&self) -> usize {
+        match *self {
+            Skew::Backwards { index, .. } => index,
+            Skew::Jump { index, .. } => index,
+        }
+    }
+}
+
+/// Iterator yielding the skews between consecutive timestamps of the wrapped iterator.
+///
+/// See [IterExt::timestamp_skew](trait.IterExt.html#method.timestamp_skew) for more information.
+pub struct TimestampSkew<I: Iterator, D> {
+    iter: I,
+    max_jump: D,
+    last: Option<I::Item>,
+    // the index of the next timestamp
+    index: usize,
+}
+
+impl<I: Iterator, D> TimestampSkew<I, D> where I::Item: Copy + PartialOrd + Sub<Output = D>, D: PartialOrd {
+    /// This creates a new TimestampSkew iterator. Usually you should be using
+    /// [IterExt::timestamp_skew](trait.IterExt.html#method.timestamp_skew).
+    pub fn new(iter: I, max_jump: D) -> TimestampSkew<I, D> {
+        TimestampSkew {
+            iter,
+            max_jump,
+            last: None,
+            index: 0,
+        }
+    }
+}
+
+impl<I: Iterator, D> Iterator for TimestampSkew<I, D> where I::Item: Copy + PartialOrd + Sub<Output = D>, D: PartialOrd {
+    type Item = Skew<D>;
+
+    fn next(&mut self) -> Option<Skew<D>> {
+        for current in &mut self.iter {
+            let index = self.index;
+            self.index += 1;
+            let last = match self.last.replace(current) {
+                Some(last) => last,
+                None => continue,
+            };
+
+            // subtract the smaller timestamp, unsigned timestamps can't go negative
+            if current < last {
+                return Some(Skew::Backwards { index, delta: last - current });
+            }
+            let delta = current - last;
+            if delta > self.max_jump {
+                return Some(Skew::Jump { index, delta });
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
@@ -1,8 +1,14 @@
+use std::borrow::Cow;
 use std::cell::{Cell, UnsafeCell};
+use std::convert::TryInto;
+use std::error::Error;
 use std::fmt;
 use std::marker::PhantomData;
-#[cfg(nightly)]
-use std::iterator::FusedIterator;
+use std::mem;
+use std::thread;
+use std::ops::{ControlFlow, Sub};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::iter::{Flatten, FusedIterator, Peekable};
 
 /// This holds the backing allocation for the `Window` of an `Adaptor`.
 ///
@@ -13,6 +19,14 @@ pub struct Storage<T> {
     window_offset: Cell<usize>,
     /// acts as a refcount
     uniquely_owned: Cell<bool>,
+    // set while an Adaptor created by Adaptor::from_shared uses this storage
+    attached: Cell<bool>,
+    // set when a panic unwound through an Adaptor using this storage
+    poisoned: Cell<bool>,
+    // number of elements pushed since the last clear
+    pushed: Cell<usize>,
+    // identifies the storage in panic messages
+    debug_name: Option<String>,
     data: UnsafeCell<Vec<T>>,
 }
 
@@ -32,36 +46,237 @@ impl<T> Storage<T> {
     /// See [sliding_windows](index.html) for more information.
     pub fn from_vec<S: Into<Vec<T>>>(vec: S, window_size: usize) -> Storage<T> {
         let mut vec = vec.into();
+        vec.clear();
         let missing: isize = window_size as isize - vec.capacity() as isize;
         if missing > 0 {
             vec.reserve_exact(missing as usize);
         }
 
         Storage {
-            window_size: window_size,
+            window_size,
             window_offset: Cell::new(0),
             uniquely_owned: Cell::new(true),
+            attached: Cell::new(false),
+            poisoned: Cell::new(false),
+            pushed: Cell::new(0),
+            debug_name: None,
             data: UnsafeCell::new(vec)
         }
     }
 
-    fn new_window<'a>(&'a self) -> Window<'a, T> {
+    /// The number of elements of a full `Window`.
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Change the window size. This clears the storage and reserves memory for a full window
+    /// if necessary.
+    ///
+    /// ```
+    /// use sliding_windows::Storage;
+    ///
+    /// let mut storage: Storage<u32> = Storage::default();
+    /// storage.set_window_size(4);
+    /// assert_eq!(storage.window_size(), 4);
+    /// assert!(storage.capacity() >= 4);
+    /// ```
+    pub fn set_window_size(&mut self, window_size: usize) {
+        self.clear();
+        self.window_size = window_size;
+        let data = unsafe { &mut *self.data.get() };
+        data.reserve_exact(window_size);
+    }
+
+    /// The number of elements the storage can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        unsafe { (*self.data.get()).capacity() }
+    }
+
+    /// Reserve capacity for at least `additional` more elements than currently buffered,
+    /// see `Vec::reserve`.
+    pub fn reserve(&mut self, additional: usize) {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        data.reserve(additional);
+    }
+
+    /// Shrink the allocation as much as possible, while keeping room for a full window.
+    pub fn shrink_to_fit(&mut self) {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        data.shrink_to(self.window_size);
+    }
+
+    /// The number of bytes this storage allocated on the heap.
+    ///
+    /// This doesn't include memory owned by the elements, see
+    /// [heap_size_hint_with](#method.heap_size_hint_with) for that.
+    pub fn heap_size_hint(&self) -> usize {
+        let name = self.debug_name.as_ref().map_or(0, |name| name.capacity());
+        self.capacity() * mem::size_of::<T>() + name
+    }
+
+    /// The number of bytes this storage allocated on the heap plus the heap memory owned by the
+    /// buffered elements, as reported by `element_size`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<String> = Storage::new(2);
+    /// (0..3).map(|x| "x".repeat(x)).sliding_windows(&mut storage).count();
+    ///
+    /// let deep = storage.heap_size_hint_with(|s| s.capacity());
+    /// assert_eq!(deep, storage.heap_size_hint() + 3);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if a `Window` into this storage is still alive.
+    pub fn heap_size_hint_with<F>(&self, element_size: F) -> usize where F: FnMut(&T) -> usize {
+        self.assert_no_window();
+        let data = unsafe { &*self.data.get() };
+        self.heap_size_hint() + data.iter().map(element_size).sum::<usize>()
+    }
+
+    /// Set a name which identifies this `Storage` in panic messages,
+    /// useful for pipelines with several windowed stages.
+    ///
+    /// ```
+    /// use sliding_windows::Storage;
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// storage.set_debug_name("moving average");
+    /// assert_eq!(storage.debug_name(), Some("moving average"));
+    /// ```
+    pub fn set_debug_name(&mut self, name: &str) {
+        self.debug_name = Some(name.to_owned());
+    }
+
+    /// The name set via [set_debug_name](#method.set_debug_name).
+    pub fn debug_name(&self) -> Option<&str> {
+        self.debug_name.as_ref().map(|name| &name[..])
+    }
+
+    /// Checks the internal invariants of the storage and panics with a description of the
+    /// first violated one: no `Window` into the storage is marked alive, the ring buffer
+    /// offset lies within the buffered elements and only a full window is rotated.
+    ///
+    /// The checks only run in debug builds or with the `strict-checks` feature, which also
+    /// runs them before every push and every window. A violation means the storage was
+    /// corrupted, e.g. by leaking a `Window` with `mem::forget`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// (0..10).sliding_windows(&mut storage).count();
+    /// storage.debug_validate();
+    /// ```
+    pub fn debug_validate(&mut self) {
+        if cfg!(any(debug_assertions, feature = "strict-checks")) {
+            assert!(self.uniquely_owned.get(),
+                    "storage invariant violated: a Window is still marked alive ({})", self.context());
+            assert!(!self.attached.get(),
+                    "storage invariant violated: still attached to a shared Adaptor ({})", self.context());
+            self.check_invariants();
+        }
+    }
+
+    // the invariants of `debug_validate` which hold while a Window is alive
+    fn check_invariants(&self) {
+        let len = self.len();
+        let window_offset = self.window_offset.get();
+        assert!(self.pushed.get() >= len,
+                "storage invariant violated: {} elements buffered but {} pushed ({})",
+                len, self.pushed.get(), self.context());
+        assert!(window_offset == 0 || window_offset < len,
+                "storage invariant violated: offset {} out of bounds ({})", window_offset, self.context());
+        assert!(window_offset == 0 || len == self.window_size,
+                "storage invariant violated: rotated at offset {} without a full window ({})",
+                window_offset, self.context());
+    }
+
+    // describes the configuration and state of the storage for panic messages
+    fn context(&self) -> String {
+        let len = self.len();
+        let name = match self.debug_name {
+            Some(ref name) => format!("storage \"{}\", ", name),
+            None => String::new(),
+        };
+
+        format!("{}window size {}, {}/{} elements buffered, live window starts at element {}",
+                name, self.window_size, len, self.window_size, self.pushed.get() - len)
+    }
+
+    // panic if a Window into this storage is still alive
+    fn assert_no_window(&self) {
+        if !self.uniquely_owned.get() {
+            #[cfg(feature = "tracing")]
+            ::tracing::error!(window_size = self.window_size, context = %self.context(),
+                              "next() called before previous Window went out of scope");
+            panic!("next() called before previous Window went out of scope ({})", self.context());
+        }
+    }
+
+    pub(crate) fn new_window<'a>(&'a self) -> Window<'a, T> {
+        self.window_with_capacity(self.window_size)
+    }
+
+    // create a window over all buffered elements, reporting `capacity` as its capacity
+    pub(crate) fn window_with_capacity<'a>(&'a self, capacity: usize) -> Window<'a, T> {
         // assert that the last window went out of scope
-        assert!(self.uniquely_owned.get(), "next() called before previous Window went out of scope");
+        self.assert_no_window();
+        #[cfg(feature = "strict-checks")]
+        self.check_invariants();
         let data = unsafe { &mut *self.data.get() };
         let window_offset = self.window_offset.get();
+        #[cfg(feature = "strict-checks")]
+        assert!(data.len() <= capacity,
+                "storage invariant violated: window of {} elements exceeds its capacity {} ({})",
+                data.len(), capacity, self.context());
 
         self.uniquely_owned.set(false);
 
-        Window { drop_flag: &self.uniquely_owned, data: &mut data[..], window_offset: window_offset }
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!(window_size = self.window_size, len = data.len(), window_offset, "window created");
+
+        Window {
+            drop_flag: &self.uniquely_owned,
+            storage_offset: &self.window_offset,
+            data: &mut data[..],
+            window_offset,
+            capacity,
+        }
+    }
+
+    // create a window over the newest `fill` buffered elements, making the buffer contiguous
+    pub(crate) fn suffix_window(&self, fill: usize) -> Window<'_, T> {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        data.rotate_left(self.window_offset.get());
+        self.window_offset.set(0);
+        let start = data.len() - fill.min(data.len());
+
+        self.uniquely_owned.set(false);
+
+        Window {
+            drop_flag: &self.uniquely_owned,
+            storage_offset: &self.window_offset,
+            data: &mut data[start..],
+            window_offset: 0,
+            capacity: self.window_size,
+        }
     }
 
     // push value onto self, return true if window is full (for initialization)
     // this assumes that data.capacity >= self.window_size
-    fn push(&self, elt: T) -> bool {
-        assert!(self.uniquely_owned.get(), "next() called before previous Window went out of scope");
+    pub(crate) fn push(&self, elt: T) -> bool {
+        self.assert_no_window();
+        #[cfg(feature = "strict-checks")]
+        self.check_invariants();
         let data = unsafe { &mut *self.data.get() };
         let window_offset = self.window_offset.get();
+        self.pushed.set(self.pushed.get() + 1);
 
         // if storage is not full simply push the element
         // this is only the case when filling storage initially
@@ -74,32 +289,167 @@ impl<T> Storage<T> {
         debug_assert!(data.len() == self.window_size);
 
         // the storage is full, overwrite the last element
-        let new_offset;
-        if window_offset >= (self.window_size - 1) {
-            new_offset = 0;
+        let new_offset = if window_offset >= (self.window_size - 1) {
+            0
         } else {
-            new_offset = window_offset + 1;
-        }
+            window_offset + 1
+        };
 
         data[window_offset] = elt;
         self.window_offset.set(new_offset);
+
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!(window_size = self.window_size, window_offset, "element evicted");
         true
     }
 
+    // replace the buffered elements with clones of `elts`, which must fit the capacity
+    pub(crate) fn refill(&self, elts: &[T]) where T: Clone {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        assert!(elts.len() <= data.capacity(), "refill exceeds capacity ({})", self.context());
+        data.clear();
+        data.extend_from_slice(elts);
+        self.window_offset.set(0);
+        self.pushed.set(self.pushed.get() + elts.len());
+    }
+
+    /// Whether an `Adaptor` created by [Adaptor::from_shared](struct.Adaptor.html#method.from_shared)
+    /// currently uses this storage.
+    pub fn is_attached(&self) -> bool {
+        self.attached.get()
+    }
+
+    /// Whether a panic unwound through an `Adaptor` using this storage, e.g. a panicking
+    /// input iterator or element destructor. Like a poisoned `Mutex`, the buffered elements
+    /// may be inconsistent and new adaptors refuse to use the storage until
+    /// [clear_poison](#method.clear_poison) is called.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.get()
+    }
+
+    /// Clears the buffered elements and the poisoned flag, so the storage can be reused.
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     (0..5).map(|x| if x == 3 { panic!("bad input") } else { x })
+    ///         .sliding_windows(&mut storage)
+    ///         .count()
+    /// }));
+    /// assert!(result.is_err());
+    /// assert!(storage.is_poisoned());
+    ///
+    /// storage.clear_poison();
+    /// assert_eq!((0..5).sliding_windows(&mut storage).count(), 4);
+    /// ```
+    pub fn clear_poison(&mut self) {
+        self.clear();
+        self.poisoned.set(false);
+    }
+
+    // mark the storage as used by a shared Adaptor and clear it
+    fn attach(&self) -> Result<Attachment<'_>, StorageInUse> {
+        if self.attached.get() {
+            return Err(StorageInUse { context: self.context() });
+        }
+        self.clear_shared();
+        self.attached.set(true);
+        Ok(Attachment { attached: &self.attached })
+    }
+
+    // remove the buffered elements in logical order, keeping the allocation
+    fn take_buffered(&self) -> Vec<T> {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        data.rotate_left(self.window_offset.get());
+        self.window_offset.set(0);
+        let mut buffered = Vec::with_capacity(data.len());
+        buffered.append(data);
+        buffered
+    }
+
+    // the number of buffered elements
+    pub(crate) fn len(&self) -> usize {
+        unsafe { (*self.data.get()).len() }
+    }
+
+    // push value onto self, growing the allocation beyond the window size if necessary
+    pub(crate) fn push_unbounded(&self, elt: T) {
+        self.assert_no_window();
+        let data = unsafe { &mut *self.data.get() };
+        debug_assert!(self.window_offset.get() == 0);
+        self.pushed.set(self.pushed.get() + 1);
+        data.push(elt);
+    }
+
     // clear backing storage
     fn clear(&mut self) {
-        assert!(self.uniquely_owned.get(), "next() called before previous Window went out of scope");
+        self.clear_shared();
+    }
+
+    // clear backing storage, the caller has to ensure no other Adaptor uses it
+    pub(crate) fn clear_shared(&self) {
+        self.assert_no_window();
         let data = unsafe { &mut *self.data.get() };
         data.clear();
+        self.window_offset.set(0);
+        self.pushed.set(0);
+    }
+
+    /// Consumes the `Storage` and returns the buffered elements in logical order,
+    /// i.e. oldest first, reusing the allocation.
+    ///
+    /// Prefer this over the `From<Storage<T>> for Vec<T>` impl, which keeps the physical
+    /// order of the ring buffer and is only kept for compatibility.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// (0..5).sliding_windows(&mut storage).count();
+    ///
+    /// assert_eq!(storage.into_vec(), [2, 3, 4]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if a `Window` into this storage is still alive.
+    pub fn into_vec(self) -> Vec<T> {
+        assert!(self.uniquely_owned.get(),
+                "Storage dereferenced before previous Window went out of scope ({})", self.context());
+        let window_offset = self.window_offset.get();
+        let mut data = self.data.into_inner();
+        data.rotate_left(window_offset);
+        data
     }
 }
 
-impl<T> Into<Vec<T>> for Storage<T> {
-    fn into(self) -> Vec<T> {
-        assert!(self.uniquely_owned.get(), "Storage dereferenced before previous Window went out of scope");
-        unsafe {
-            self.data.into_inner()
-        }
+// a storage which witnessed a panic in `next` is poisoned and only accessible again after
+// clear_poison, but a panic while mutating a window through `iter_mut` may leave the elements
+// half-modified, like with a plain `&mut [T]`
+impl<T: UnwindSafe> UnwindSafe for Storage<T> {}
+impl<T: RefUnwindSafe> RefUnwindSafe for Storage<T> {}
+
+/// Creates an empty `Storage` with window size 0, which doesn't allocate.
+/// Use [set_window_size](struct.Storage.html#method.set_window_size) to configure it later.
+impl<T> Default for Storage<T> {
+    fn default() -> Storage<T> {
+        Storage::new(0)
+    }
+}
+
+/// Returns the buffered elements in the physical order of the ring buffer, which is rotated
+/// once the window slid. This is kept for compatibility, use
+/// [Storage::into_vec](struct.Storage.html#method.into_vec) to get them in logical order.
+impl<T> From<Storage<T>> for Vec<T> {
+    fn from(storage: Storage<T>) -> Vec<T> {
+        assert!(storage.uniquely_owned.get(),
+                "Storage dereferenced before previous Window went out of scope ({})", storage.context());
+        storage.data.into_inner()
     }
 }
 
@@ -132,17 +482,60 @@ impl<T> Into<Vec<T>> for Storage<T> {
 /// }
 /// ```
 ///
+/// The lifetime `'a` is the borrow of the storage. Iterators obtained via `iter`, `iter_mut`
+/// or `IntoIterator` for `&Window` and `&mut Window` borrow the window itself, so they
+/// can't outlive it, but helpers taking a window by reference can return them.
+///
 /// See [sliding_windows](index.html) for more information.
 pub struct Window<'a, T: 'a> {
     drop_flag: &'a Cell<bool>,
+    // the offset of the storage, kept in sync when the window is made contiguous
+    storage_offset: &'a Cell<usize>,
     // index of first element
     window_offset: usize,
+    // the window size of the storage
+    capacity: usize,
     data: &'a mut [T],
 }
 
 impl<'a, T> Window<'a, T>
 {
-    pub fn iter(&self) -> WindowIter<T> {
+    /// The number of elements in this window. This is the window size, unless the window was
+    /// yielded before the storage was full, see [Adaptor::min_fill](struct.Adaptor.html#method.min_fill).
+    pub fn fill(&self) -> usize {
+        self.data.len()
+    }
+
+    /// The number of elements of a full window, i.e. the window size of the storage.
+    ///
+    /// Statistics over windows which may not be full should divide by
+    /// [fill](#method.fill) instead:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<f64> = Storage::new(4);
+    /// let means: Vec<f64> = vec![2.0, 4.0, 6.0].into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .map(|window| {
+    ///         assert_eq!((window.fill(), window.capacity()), (3, 4));
+    ///         window.iter().sum::<f64>() / window.fill() as f64
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(means, [4.0]);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Whether the window holds [capacity](#method.capacity) elements.
+    pub fn is_full(&self) -> bool {
+        self.data.len() == self.capacity
+    }
+
+    /// Iterates over the elements in logical order, for as long as the window is borrowed.
+    pub fn iter(&self) -> WindowIter<'_, T> {
         WindowIter {
             data: self.data,
             current_index: self.window_offset,
@@ -150,7 +543,9 @@ impl<'a, T> Window<'a, T>
         }
     }
 
-    pub fn iter_mut(&mut self) -> WindowIterMut<T> {
+    /// Iterates mutably over the elements in logical order, for as long as the window is
+    /// borrowed.
+    pub fn iter_mut(&mut self) -> WindowIterMut<'_, T> {
         WindowIterMut {
             data: self.data.as_mut_ptr(),
             data_len: self.data.len(),
@@ -159,13 +554,308 @@ impl<'a, T> Window<'a, T>
             _p: PhantomData
         }
     }
+
+    /// Calls `f` on every element in logical order, allowing to modify it.
+    ///
+    /// This covers most uses of `iter_mut()` without going through the raw pointer iterator.
+    /// Changes persist into the following windows.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<f64> = Storage::new(2);
+    /// let mut windows = (1..4).map(f64::from).sliding_windows(&mut storage);
+    ///
+    /// windows.next().unwrap().map_in_place(|x| *x *= 0.5);
+    /// assert_eq!(windows.next().unwrap(), &[1.0, 3.0][..]);
+    /// ```
+    pub fn map_in_place<F>(&mut self, mut f: F) where F: FnMut(&mut T) {
+        let (newer, older) = self.data.split_at_mut(self.window_offset);
+        for x in older.iter_mut().chain(newer) {
+            f(x);
+        }
+    }
+
+    /// Calls `f` on every element in logical order together with the element of `kernel` at
+    /// the same position, e.g. to apply weights or masks.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let weights = [1, 10, 100];
+    /// let weighted: Vec<u32> = (1..5).sliding_windows(&mut storage).map(|window| {
+    ///     let mut sum = 0;
+    ///     window.zip_kernel(&weights, |x, w| sum += x * w);
+    ///     sum
+    /// }).collect();
+    ///
+    /// assert_eq!(weighted, [321, 432]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `kernel` and the window differ in length.
+    pub fn zip_kernel<U, F>(&self, kernel: &[U], mut f: F) where F: FnMut(&T, &U) {
+        assert_eq!(kernel.len(), self.data.len(), "kernel length differs from window length");
+        let (older, newer) = self.segments();
+        for (x, k) in older.iter().chain(newer).zip(kernel) {
+            f(x, k);
+        }
+    }
+
+    /// Whether the elements are sorted in non-decreasing order, like `slice::is_sorted`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let sorted: Vec<bool> = vec![1, 2, 2, 1, 0].into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .map(|window| window.is_sorted())
+    ///     .collect();
+    ///
+    /// assert_eq!(sorted, [true, false, false]);
+    /// ```
+    pub fn is_sorted(&self) -> bool where T: PartialOrd {
+        self.is_monotonic_increasing()
+    }
+
+    /// Whether every element is greater than or equal to its predecessor.
+    pub fn is_monotonic_increasing(&self) -> bool where T: PartialOrd {
+        self.all_pairs(|a, b| a <= b)
+    }
+
+    /// Whether every element is greater than its predecessor.
+    pub fn is_strictly_increasing(&self) -> bool where T: PartialOrd {
+        self.all_pairs(|a, b| a < b)
+    }
+
+    /// Whether every element is less than or equal to its predecessor.
+    pub fn is_monotonic_decreasing(&self) -> bool where T: PartialOrd {
+        self.all_pairs(|a, b| a >= b)
+    }
+
+    /// Whether every element is less than its predecessor.
+    pub fn is_strictly_decreasing(&self) -> bool where T: PartialOrd {
+        self.all_pairs(|a, b| a > b)
+    }
+
+    /// Whether `pred` holds for all elements, `true` for an empty window.
+    ///
+    /// Like [any](#method.any) and [count_where](#method.count_where) this walks the two
+    /// contiguous parts of the ring buffer, avoiding the wrap-around check of `WindowIter`.
+    pub fn all<F>(&self, mut pred: F) -> bool where F: FnMut(&T) -> bool {
+        let (older, newer) = self.segments();
+        older.iter().all(&mut pred) && newer.iter().all(pred)
+    }
+
+    /// Whether `pred` holds for any element.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<f64> = Storage::new(2);
+    /// let valid: Vec<bool> = vec![1.0, f64::NAN, 2.0, 3.0].into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .map(|window| !window.any(|x| x.is_nan()))
+    ///     .collect();
+    ///
+    /// assert_eq!(valid, [false, false, true]);
+    /// ```
+    pub fn any<F>(&self, mut pred: F) -> bool where F: FnMut(&T) -> bool {
+        let (older, newer) = self.segments();
+        older.iter().any(&mut pred) || newer.iter().any(pred)
+    }
+
+    /// The number of elements for which `pred` holds.
+    pub fn count_where<F>(&self, mut pred: F) -> usize where F: FnMut(&T) -> bool {
+        let (older, newer) = self.segments();
+        older.iter().filter(|x| pred(x)).count() + newer.iter().filter(|x| pred(x)).count()
+    }
+
+    /// Folds the elements in logical order, passing the position of each element in the window
+    /// to `f`, e.g. for positional weights like a triangular filter.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<f64> = Storage::new(3);
+    /// let triangular: Vec<f64> = vec![4.0, 8.0, 4.0, 0.0].into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .map(|window| window.fold_with_index(0.0, |acc, i, x| acc + x * [0.25, 0.5, 0.25][i]))
+    ///     .collect();
+    ///
+    /// assert_eq!(triangular, [6.0, 4.0]);
+    /// ```
+    pub fn fold_with_index<B, F>(&self, init: B, mut f: F) -> B where F: FnMut(B, usize, &T) -> B {
+        let (older, newer) = self.segments();
+        let acc = older.iter().enumerate().fold(init, |acc, (i, x)| f(acc, i, x));
+        newer.iter().enumerate().fold(acc, |acc, (i, x)| f(acc, older.len() + i, x))
+    }
+
+    /// The elements in logical order as one slice, which borrows the storage if the window
+    /// happens to be contiguous and clones the elements otherwise.
+    ///
+    /// Use [make_contiguous](#method.make_contiguous) to avoid the copy if the window is mutable.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    /// use std::borrow::Cow;
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut windows = (0..5).sliding_windows(&mut storage);
+    ///
+    /// assert!(matches!(windows.next().unwrap().as_cow(), Cow::Borrowed(&[0, 1, 2])));
+    /// assert_eq!(windows.next().unwrap().as_cow(), Cow::<[u32]>::Owned(vec![1, 2, 3]));
+    /// ```
+    pub fn as_cow(&self) -> Cow<'_, [T]> where T: Clone {
+        match self.segments() {
+            (older, []) => Cow::Borrowed(older),
+            (older, newer) => {
+                let mut owned = Vec::with_capacity(self.fill());
+                owned.extend_from_slice(older);
+                owned.extend_from_slice(newer);
+                Cow::Owned(owned)
+            }
+        }
+    }
+
+    /// Rotates the buffered elements so that they are stored in logical order and returns
+    /// them as one slice. Later windows of the same storage are not affected.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut windows = (1..6).sliding_windows(&mut storage);
+    ///
+    /// assert_eq!(windows.nth(2).unwrap().make_contiguous(), [3, 4, 5]);
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.data.rotate_left(self.window_offset);
+        self.window_offset = 0;
+        self.storage_offset.set(0);
+        self.data
+    }
+
+    /// The elements in logical order as an array, which is useful for small windows
+    /// of a [FixedStorage](struct.FixedStorage.html):
+    ///
+    /// ```
+    /// use sliding_windows::{FixedStorage, IterExt};
+    ///
+    /// let mut storage: FixedStorage<u32, 3> = FixedStorage::new();
+    /// let peaks: Vec<u32> = [1, 3, 2, 5, 4].iter().cloned().sliding_windows(&mut storage)
+    ///     .filter_map(|mut window| {
+    ///         let &[a, b, c] = window.as_array();
+    ///         if a < b && b > c { Some(b) } else { None }
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(peaks, [3, 5]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the window does not hold exactly `N` elements.
+    pub fn as_array<const N: usize>(&mut self) -> &[T; N] {
+        self.as_mut_array()
+    }
+
+    /// Like [as_array](#method.as_array), but allows to modify the elements.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the window does not hold exactly `N` elements.
+    pub fn as_mut_array<const N: usize>(&mut self) -> &mut [T; N] {
+        let fill = self.fill();
+        assert!(fill == N, "window holds {} elements, not {}", fill, N);
+        self.make_contiguous().try_into().expect("length was checked")
+    }
+
+    // a window over the buffer of a storage defined outside this module, `drop_flag` is set
+    // when the window is dropped and `storage_offset` when it is made contiguous
+    pub(crate) fn from_parts(drop_flag: &'a Cell<bool>, storage_offset: &'a Cell<usize>, data: &'a mut [T],
+                             window_offset: usize, capacity: usize) -> Window<'a, T> {
+        Window {
+            drop_flag,
+            storage_offset,
+            window_offset,
+            capacity,
+            data,
+        }
+    }
+
+    // the elements in logical order as the older and the newer contiguous segment
+    fn segments(&self) -> (&[T], &[T]) {
+        let (newer, older) = self.data.split_at(self.window_offset);
+        (older, newer)
+    }
+
+    // whether `f` holds for all pairs of consecutive elements
+    fn all_pairs<F>(&self, mut f: F) -> bool where F: FnMut(&T, &T) -> bool {
+        let (older, newer) = self.segments();
+        let seam = match (older.last(), newer.first()) {
+            (Some(a), Some(b)) => f(a, b),
+            _ => true,
+        };
+
+        seam && older.windows(2).all(|pair| f(&pair[0], &pair[1]))
+             && newer.windows(2).all(|pair| f(&pair[0], &pair[1]))
+    }
+
+    /// Returns an object implementing `Display`, which writes the elements
+    /// in logical order separated by `sep`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let window = (0..5).sliding_windows(&mut storage).next().unwrap();
+    ///
+    /// assert_eq!(window.display_with(", ").to_string(), "0, 1, 2");
+    /// assert_eq!(window.to_string(), "0 1 2");
+    /// ```
+    pub fn display_with<'w>(&'w self, sep: &'w str) -> DisplayWith<'w, 'a, T> {
+        DisplayWith { window: self, sep }
+    }
 }
 
 impl<'a, T> fmt::Debug for Window<'a, T> where T: fmt::Debug
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("Window")?;
-        return f.debug_list().entries(self.into_iter()).finish();
+        f.debug_list().entries(self).finish()
+    }
+}
+
+/// Writes the elements in logical order separated by a single space.
+impl<'a, T> fmt::Display for Window<'a, T> where T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_with(" ").fmt(f)
+    }
+}
+
+/// Helper to display a `Window` with a custom separator.
+///
+/// See [Window::display_with](struct.Window.html#method.display_with) for more information.
+pub struct DisplayWith<'w, 'a: 'w, T: 'a> {
+    window: &'w Window<'a, T>,
+    sep: &'w str,
+}
+
+impl<'w, 'a, T> fmt::Display for DisplayWith<'w, 'a, T> where T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, x) in self.window.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            x.fmt(f)?;
+        }
+        Ok(())
     }
 }
 
@@ -176,6 +866,9 @@ impl<'a, T> Drop for Window<'a, T> {
     }
 }
 
+// the cells are only written by dropping the window and by methods taking `&mut self`
+impl<'a, T: RefUnwindSafe> RefUnwindSafe for Window<'a, T> {}
+
 impl<'a, 'b, T> PartialEq<&'b [T]> for Window<'a, T> where T: PartialEq
 {
     fn eq(&self, other: &&'b [T]) -> bool {
@@ -187,15 +880,27 @@ impl<'a, 'b, T> PartialEq<&'b [T]> for Window<'a, T> where T: PartialEq
     }
 }
 
-impl<'a, T> IntoIterator for &'a Window<'a, T>
+// the borrow of the window ('w) is independent of the borrow of the storage ('a), so
+// iterators only live as long as the window reference they were created from
+impl<'w, 'a, T> IntoIterator for &'w Window<'a, T>
 {
-    type Item = &'a T;
-    type IntoIter = WindowIter<'a, T>;
+    type Item = &'w T;
+    type IntoIter = WindowIter<'w, T>;
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
+impl<'w, 'a, T> IntoIterator for &'w mut Window<'a, T>
+{
+    type Item = &'w mut T;
+    type IntoIter = WindowIterMut<'w, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the elements of a `Window`, see [Window::iter](struct.Window.html#method.iter).
 pub struct WindowIter<'a, T: 'a>
 {
     data: &'a [T],
@@ -204,17 +909,42 @@ pub struct WindowIter<'a, T: 'a>
     iteration_num: usize,
 }
 
+impl<'a, T> WindowIter<'a, T> {
+    /// Returns the remaining elements as two slices, which hold them in logical order when
+    /// concatenated. The second slice is empty unless the remainder wraps around the end of
+    /// the ring buffer.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(4);
+    /// let window = (0..6).sliding_windows(&mut storage).nth(2).unwrap();
+    ///
+    /// let mut iter = window.iter();
+    /// iter.next();
+    /// assert_eq!(iter.as_slice_pair(), (&[3][..], &[4, 5][..]));
+    /// ```
+    pub fn as_slice_pair(&self) -> (&'a [T], &'a [T]) {
+        let remaining = self.data.len() - self.iteration_num;
+        let first_len = remaining.min(self.data.len() - self.current_index);
+        let first = &self.data[self.current_index..self.current_index + first_len];
+        (first, &self.data[..remaining - first_len])
+    }
+}
+
 impl<'a, T> Iterator for WindowIter<'a, T>
 {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let current_element = &self.data[self.current_index];
-
         if self.iteration_num >= self.data.len() {
             // the end was reached
             return None;
-        } else if self.current_index >= (self.data.len() - 1) {
+        }
+
+        let current_element = &self.data[self.current_index];
+
+        if self.current_index >= (self.data.len() - 1) {
             // wrap around if the increment would create an invalid index
             self.current_index = 0;
         } else {
@@ -226,14 +956,16 @@ impl<'a, T> Iterator for WindowIter<'a, T>
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.data.len(), Some(self.data.len()))
+        let remaining = self.data.len() - self.iteration_num;
+        (remaining, Some(remaining))
     }
 }
 
 impl<'a, T> ExactSizeIterator for WindowIter<'a, T> {}
-#[cfg(nightly)]
 impl<'a, T> FusedIterator for WindowIter<'a, T> {}
 
+/// Mutable iterator over the elements of a `Window`, see
+/// [Window::iter_mut](struct.Window.html#method.iter_mut).
 pub struct WindowIterMut<'a, T: 'a>
 {
     data: *mut T,
@@ -249,12 +981,14 @@ impl<'a, T> Iterator for WindowIterMut<'a, T>
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let current_element = unsafe { self.data.offset(self.current_index as isize).as_mut().unwrap() };
-
         if self.iteration_num >= self.data_len {
             // the end was reached
             return None;
-        } else if self.current_index >= (self.data_len - 1) {
+        }
+
+        let current_element = unsafe { self.data.add(self.current_index).as_mut().unwrap() };
+
+        if self.current_index >= (self.data_len - 1) {
             // wrap around if the increment would create an invalid index
             self.current_index = 0;
         } else {
@@ -266,85 +1000,946 @@ impl<'a, T> Iterator for WindowIterMut<'a, T>
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.data_len, Some(self.data_len))
+        let remaining = self.data_len - self.iteration_num;
+        (remaining, Some(remaining))
     }
 }
 
 impl<'a, T> ExactSizeIterator for WindowIterMut<'a, T> {}
-#[cfg(nightly)]
 impl<'a, T> FusedIterator for WindowIterMut<'a, T> {}
 
 // TODO add other stuff like DoubleEndedIterator etc.
 
+/// The interface an `Adaptor` uses to drive its backing storage.
+///
+/// The crate ships these storages:
+///
+/// - [Storage](struct.Storage.html), with a window size chosen at runtime
+/// - [FixedStorage](struct.FixedStorage.html), a `Storage` with a window size fixed at compile time
+/// - [ArrayStorage](struct.ArrayStorage.html), which keeps the elements in an inline array and
+///   never allocates
+///
+/// Implementing this trait for a wrapper around a `Storage` lets tests inject instrumented
+/// storages, e.g. ones counting pushes or reporting a full window early.
+pub trait AsWindowStorage<T> {
+    /// The number of elements of a full `Window`, an `Adaptor` yields nothing if this is 0.
+    fn window_size(&self) -> usize;
+
+    /// Remove all buffered elements, called when an `Adaptor` is created or starts over.
+    fn clear(&self);
+
+    /// Push `elt`, returning true if a `Window` should be yielded.
+    fn push(&self, elt: T) -> bool;
+
+    /// Create the `Window` over the buffered elements.
+    fn new_window(&self) -> Window<'_, T>;
+
+    /// Mark the storage as poisoned, called when a panic unwinds through `Adaptor::next`.
+    /// Does nothing by default.
+    fn poison(&self) {}
+
+    /// Panic if the storage was poisoned, called before an `Adaptor` uses the storage.
+    /// Does nothing by default.
+    fn assert_not_poisoned(&self) {}
+
+    /// Panic if a `Window` into the storage is still alive, called before `Adaptor::next`
+    /// touches the storage, so the misuse doesn't poison it. Does nothing by default.
+    fn assert_no_window(&self) {}
+
+    /// Create a `Window` over the newest `fill` buffered elements, for the trailing windows of
+    /// [Adaptor::with_partial_suffix](struct.Adaptor.html#method.with_partial_suffix).
+    /// Returns `None` by default, i.e. no trailing windows are yielded.
+    fn new_suffix_window(&self, fill: usize) -> Option<Window<'_, T>> {
+        let _ = fill;
+        None
+    }
+}
+
+impl<T> AsWindowStorage<T> for Storage<T> {
+    fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    fn clear(&self) {
+        Storage::clear_shared(self)
+    }
+
+    fn push(&self, elt: T) -> bool {
+        Storage::push(self, elt)
+    }
+
+    fn new_window(&self) -> Window<'_, T> {
+        Storage::new_window(self)
+    }
+
+    fn poison(&self) {
+        self.poisoned.set(true);
+    }
+
+    fn assert_not_poisoned(&self) {
+        assert!(!self.poisoned.get(),
+                "storage poisoned by a panic in an earlier Adaptor, call clear_poison() to reuse it ({})",
+                self.context());
+    }
+
+    fn assert_no_window(&self) {
+        Storage::assert_no_window(self)
+    }
+
+    fn new_suffix_window(&self, fill: usize) -> Option<Window<'_, T>> {
+        Some(Storage::suffix_window(self, fill))
+    }
+}
+
+/// What an `Adaptor` does if its input ends before the first window is full.
+///
+/// See [Adaptor::on_short](struct.Adaptor.html#method.on_short) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnShort {
+    /// Yield a single window holding all elements of the input. This is the default.
+    YieldPartial,
+    /// Yield no window at all.
+    Empty,
+    /// Panic, for pipelines where a truncated input is a bug. This includes empty inputs.
+    Error,
+}
+
+// poisons the storage if a panic unwinds while the guard is alive
+struct PoisonGuard<'s, T, S: AsWindowStorage<T> + 's> {
+    storage: &'s S,
+    // a panic already unwinding when the guard was created is not ours
+    panicking: bool,
+    _elt: PhantomData<fn(T)>,
+}
+
+impl<'s, T, S: AsWindowStorage<T> + 's> PoisonGuard<'s, T, S> {
+    fn new(storage: &'s S) -> PoisonGuard<'s, T, S> {
+        PoisonGuard { storage, panicking: thread::panicking(), _elt: PhantomData }
+    }
+}
+
+impl<'s, T, S: AsWindowStorage<T> + 's> Drop for PoisonGuard<'s, T, S> {
+    fn drop(&mut self) {
+        if !self.panicking && thread::panicking() {
+            self.storage.poison();
+        }
+    }
+}
+
+// resets the attached flag of a Storage when the shared Adaptor is dropped
+struct Attachment<'a> {
+    attached: &'a Cell<bool>,
+}
+
+impl<'a> Drop for Attachment<'a> {
+    fn drop(&mut self) {
+        self.attached.set(false);
+    }
+}
+
+/// The error returned by [Adaptor::from_shared](struct.Adaptor.html#method.from_shared)
+/// if another `Adaptor` already uses the storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageInUse {
+    context: String,
+}
+
+impl fmt::Display for StorageInUse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Storage is already used by another Adaptor ({})", self.context)
+    }
+}
+
+impl Error for StorageInUse {}
+
 /// See [sliding_windows](index.html) for more information.
-pub struct Adaptor<'a, I: Iterator> where <I as Iterator>::Item: 'a {
+pub struct Adaptor<'a, I: Iterator, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a {
     iter: I,
     done: bool,
-    storage: &'a Storage<I::Item>,
+    storage: &'a S,
+    // only set for adaptors created by from_shared
+    _attachment: Option<Attachment<'a>>,
+    decay: Option<DecayFn<'a, I::Item>>,
+    on_short: OnShort,
+    min_fill: Option<usize>,
+    gap: Option<GapFn<'a, I::Item>>,
+    // number of elements pushed, saturating
+    pushed: usize,
+    // whether a window was yielded, every later window slid
+    yielded: bool,
+    emit_every: usize,
+    // slides since the last yielded window
+    skipped: usize,
+    partial_suffix: bool,
+    // the number of elements of the last yielded window
+    last_fill: usize,
+    // the fill of the next trailing window, once the input ended
+    suffix: Option<usize>,
 }
 
-impl<'a, I: Iterator> Adaptor<'a, I> {
+type DecayFn<'a, T> = Box<dyn FnMut(&mut T) + 'a>;
+
+// applies `decay` to all elements of `window` but the newest one
+fn decay_retained<T>(window: &mut Window<'_, T>, decay: &mut DecayFn<'_, T>) {
+    let retained = window.data.len() - 1;
+    for x in window.iter_mut().take(retained) {
+        decay(x);
+    }
+}
+// returns true if there is a gap before the given element
+type GapFn<'a, T> = Box<dyn FnMut(&T) -> bool + 'a>;
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> {
     /// This creates a new Adaptor. Usually you should be using
+    /// [IterExt::sliding_windows](trait.IterExt.html#method.sliding_windows).
+    ///
+    /// The storage is borrowed exclusively for the lifetime of the adaptor, so two adaptors
+    /// can't share one storage:
+    ///
+    /// ```compile_fail
+    /// use sliding_windows::{Adaptor, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let first = Adaptor::new(0..5, &mut storage);
+    /// let second = Adaptor::new(0..5, &mut storage);
+    /// first.zip(second).count();
+    /// ```
+    ///
+    /// A shared reference isn't accepted either:
+    ///
+    /// ```compile_fail
+    /// use sliding_windows::{Adaptor, Storage};
+    ///
+    /// let storage: Storage<u32> = Storage::new(3);
+    /// Adaptor::new(0..5, &storage).count();
+    /// ```
     ///
     /// See [sliding_windows](index.html) for more information.
-    pub fn new(iter: I, storage: &'a mut Storage<I::Item>) -> Adaptor<'a, I> {
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the storage is poisoned, see [Storage::is_poisoned](struct.Storage.html#method.is_poisoned).
+    pub fn new(iter: I, storage: &'a mut S) -> Adaptor<'a, I, S> {
+        storage.assert_not_poisoned();
         // in case the storage was reused
         storage.clear();
+        Adaptor::with_storage(iter, storage, None)
+    }
 
+    fn with_storage(iter: I, storage: &'a S, attachment: Option<Attachment<'a>>) -> Adaptor<'a, I, S> {
         Adaptor {
-            iter: iter,
+            iter,
             done: false,
-            storage: storage,
+            storage,
+            _attachment: attachment,
+            decay: None,
+            on_short: OnShort::YieldPartial,
+            min_fill: None,
+            gap: None,
+            pushed: 0,
+            yielded: false,
+            emit_every: 1,
+            skipped: 0,
+            partial_suffix: false,
+            last_fill: 0,
+            suffix: None,
+        }
+    }
+
+    /// Applies `decay` to every retained element each time the window slides, i.e. to all
+    /// elements but the newest one before yielding any window but the first.
+    ///
+    /// This formalizes aging elements by hand via `iter_mut()`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<f64> = Storage::new(3);
+    /// let scores = vec![8.0, 8.0, 8.0, 8.0, 8.0];
+    /// let totals: Vec<f64> = scores.into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .with_decay(|x| *x *= 0.5)
+    ///     .map(|window| window.iter().sum())
+    ///     .collect();
+    ///
+    /// // the last window holds 2.0, 4.0 and 8.0
+    /// assert_eq!(totals, [24.0, 16.0, 14.0]);
+    /// ```
+    pub fn with_decay<F>(mut self, decay: F) -> Adaptor<'a, I, S> where F: FnMut(&mut I::Item) + 'a {
+        self.decay = Some(Box::new(decay));
+        self
+    }
+
+    /// Configure what happens if the input ends before the first window is full,
+    /// [OnShort::YieldPartial](enum.OnShort.html) by default.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, OnShort, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// assert_eq!((0..2).sliding_windows(&mut storage).count(), 1);
+    /// assert_eq!((0..2).sliding_windows(&mut storage).on_short(OnShort::Empty).count(), 0);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use sliding_windows::{IterExt, OnShort, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// (0..2).sliding_windows(&mut storage).on_short(OnShort::Error).count();
+    /// ```
+    pub fn on_short(mut self, on_short: OnShort) -> Adaptor<'a, I, S> {
+        self.on_short = on_short;
+        self
+    }
+
+    /// Yield windows as soon as `min_fill` elements are buffered instead of waiting for a full
+    /// window, like the `min_periods` of pandas' rolling windows. Until the window is full, every
+    /// element yields a window holding all elements so far, see
+    /// [Window::fill](struct.Window.html#method.fill).
+    ///
+    /// If the input ends before `min_fill` elements arrived, no window is yielded, unless
+    /// [on_short](#method.on_short) is set to `OnShort::Error`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let fills: Vec<usize> = (0..5).sliding_windows(&mut storage)
+    ///     .min_fill(2)
+    ///     .map(|window| window.fill())
+    ///     .collect();
+    ///
+    /// assert_eq!(fills, [2, 3, 3, 3]);
+    /// ```
+    ///
+    /// With a `min_fill` of 1 the first windows ramp up, one element at a time, without padding
+    /// the input:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let windows: Vec<Vec<u32>> = (0..5).sliding_windows(&mut storage)
+    ///     .min_fill(1)
+    ///     .map(|window| window.iter().cloned().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(windows, [vec![0], vec![0, 1], vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `min_fill` is 0 or larger than the window size.
+    pub fn min_fill(mut self, min_fill: usize) -> Adaptor<'a, I, S> {
+        assert!(min_fill > 0 && min_fill <= self.storage.window_size(),
+                "min_fill {} must be in 1..={}", min_fill, self.storage.window_size());
+        self.min_fill = Some(min_fill);
+        self
+    }
+
+    /// Start over with an empty window whenever the `key` of two consecutive elements, e.g.
+    /// their timestamps, differs by more than `max_gap`. Windows thus never span an outage.
+    ///
+    /// Within a segment windows keep their size, so a segment shorter than the window only
+    /// yields windows with [min_fill](#method.min_fill). After a gap the adaptor behaves as
+    /// if a new input began, so [on_short](#method.on_short) applies to the last segment.
+    /// The keys are expected to be non-decreasing.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let samples = vec![(0, 1.0), (1, 2.0), (2, 3.0), (60, 4.0), (61, 5.0), (62, 6.0)];
+    /// let mut storage = Storage::new(2);
+    /// let means: Vec<f64> = samples.into_iter()
+    ///     .sliding_windows(&mut storage)
+    ///     .reset_on_gap(|&(t, _)| t, 5)
+    ///     .map(|window| window.iter().map(|&(_, x)| x).sum::<f64>() / 2.0)
+    ///     .collect();
+    ///
+    /// assert_eq!(means, [1.5, 2.5, 4.5, 5.5]);
+    /// ```
+    pub fn reset_on_gap<K, D, F>(mut self, mut key: F, max_gap: D) -> Adaptor<'a, I, S>
+        where F: FnMut(&I::Item) -> K + 'a, K: Copy + Sub<Output = D> + 'a, D: PartialOrd + 'a
+    {
+        let mut last: Option<K> = None;
+        self.gap = Some(Box::new(move |elt| {
+            let current = key(elt);
+            let gap = last.is_some_and(|last| current - last > max_gap);
+            last = Some(current);
+            gap
+        }));
+        self
+    }
+
+    /// Start over with an empty window whenever the `key` of an element differs from the key
+    /// of the previous element, so windows never span two runs of equal keys. Segments behave
+    /// as with [reset_on_gap](#method.reset_on_gap).
+    ///
+    /// See [IterExt::run_windows_by_key](trait.IterExt.html#method.run_windows_by_key) for an example.
+    pub fn reset_on_key_change<K, F>(mut self, mut key: F) -> Adaptor<'a, I, S>
+        where F: FnMut(&I::Item) -> K + 'a, K: PartialEq + 'a
+    {
+        let mut last: Option<K> = None;
+        self.gap = Some(Box::new(move |elt| {
+            let current = key(elt);
+            let changed = last.as_ref().is_some_and(|last| *last != current);
+            last = Some(current);
+            changed
+        }));
+        self
+    }
+
+    /// Only yield every `k`th window. The window still slides by one element internally, so
+    /// every yielded window holds the trailing elements and [with_decay](#method.with_decay)
+    /// is applied on every slide, but downstream work is reduced by a factor of `k`.
+    ///
+    /// The first window is yielded, slides after the last yielded window are dropped if the
+    /// input ends. If `k` exceeds the window size, the elements between two windows are
+    /// dropped without entering the storage.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let sums: Vec<u32> = (0..9).sliding_windows(&mut storage)
+    ///     .emit_every(3)
+    ///     .map(|window| window.iter().sum())
+    ///     .collect();
+    ///
+    /// // the windows [0, 1, 2], [3, 4, 5] and [6, 7, 8]
+    /// assert_eq!(sums, [3, 12, 21]);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `k` is 0.
+    pub fn emit_every(mut self, k: usize) -> Adaptor<'a, I, S> {
+        assert!(k > 0, "emit_every must be positive");
+        self.emit_every = k;
+        self
+    }
+
+    /// Consumes elements without yielding a window until the next element completes the first
+    /// window, i.e. `window_size - 1` elements or `min_fill - 1` with
+    /// [min_fill](#method.min_fill). Returns the number of consumed elements, which is less if
+    /// the input ended.
+    ///
+    /// This separates priming the window from the steady state, in which every element yields
+    /// a window:
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut windows = (0..6).sliding_windows(&mut storage);
+    /// assert_eq!(windows.warmup(), 2);
+    /// assert!(!windows.is_warm());
+    ///
+    /// let mut newest = Vec::new();
+    /// for window in windows.by_ref() {
+    ///     newest.push(*window.iter().last().unwrap());
+    /// }
+    /// assert_eq!(newest, [2, 3, 4, 5]);
+    /// assert!(windows.is_warm());
+    /// ```
+    ///
+    /// If the input ends during the warmup, the partial window is still yielded according to
+    /// [on_short](#method.on_short). Does nothing once a window was yielded.
+    pub fn warmup(&mut self) -> usize {
+        self.storage.assert_not_poisoned();
+        self.storage.assert_no_window();
+        let first = self.min_fill.unwrap_or(self.storage.window_size());
+        let mut consumed = 0;
+        let _guard = PoisonGuard::new(self.storage);
+
+        while !self.done && !self.yielded && self.pushed + 1 < first {
+            let elt = match self.iter.next() {
+                Some(elt) => elt,
+                None => break,
+            };
+            consumed += 1;
+            if let Some(ref mut gap) = self.gap {
+                if gap(&elt) {
+                    self.storage.clear();
+                    self.pushed = 0;
+                }
+            }
+            self.pushed += 1;
+            self.storage.push(elt);
         }
+        consumed
+    }
+
+    /// Whether the storage holds a full window, after which every element slides the window.
+    pub fn is_warm(&self) -> bool {
+        self.storage.window_size() > 0 && self.pushed >= self.storage.window_size()
+    }
+
+    /// Whether to yield the shorter windows at the end of the input after the last full
+    /// window, each starting one element (or [emit_every](#method.emit_every) elements) after
+    /// the previous one, instead of stopping. Trailing windows don't slide, so
+    /// [with_decay](#method.with_decay) isn't applied to them.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let windows: Vec<Vec<u32>> = (0..5).sliding_windows(&mut storage)
+    ///     .with_partial_suffix(true)
+    ///     .map(|window| window.iter().cloned().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(windows, [vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4], vec![3, 4], vec![4]]);
+    /// ```
+    ///
+    /// Custom storages yield trailing windows only if they implement
+    /// [AsWindowStorage::new_suffix_window](trait.AsWindowStorage.html#method.new_suffix_window).
+    pub fn with_partial_suffix(mut self, partial_suffix: bool) -> Adaptor<'a, I, S> {
+        self.partial_suffix = partial_suffix;
+        self
+    }
+
+    // yield the next trailing window of `with_partial_suffix`
+    fn next_suffix(&mut self) -> Option<Window<'a, I::Item>> {
+        let fill = self.suffix.unwrap_or(0);
+        if fill == 0 {
+            self.suffix = None;
+            return None;
+        }
+        self.suffix = Some(fill.saturating_sub(self.emit_every));
+        let window = self.storage.new_suffix_window(fill);
+        if window.is_none() {
+            self.suffix = None;
+        }
+        window
+    }
+
+    /// Calls `f` on every window until it returns `ControlFlow::Break`, and returns the break
+    /// value or `Continue` if the input ended, together with the number of windows processed.
+    ///
+    /// The adaptor stays usable, a later call or `next()` continues with the window after the
+    /// one which broke.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let mut windows = (0..10).sliding_windows(&mut storage);
+    ///
+    /// let (flow, processed) = windows.try_for_each_window(|window| {
+    ///     let sum: u32 = window.iter().sum();
+    ///     if sum > 6 { ControlFlow::Break(sum) } else { ControlFlow::Continue(()) }
+    /// });
+    /// assert_eq!((flow, processed), (ControlFlow::Break(7), 4));
+    ///
+    /// assert_eq!(windows.next().unwrap(), &[4, 5][..]);
+    /// ```
+    pub fn try_for_each_window<B, F>(&mut self, mut f: F) -> (ControlFlow<B>, usize)
+        where F: FnMut(&Window<'a, I::Item>) -> ControlFlow<B>
+    {
+        let mut processed = 0;
+        for window in self.by_ref() {
+            processed += 1;
+            if let ControlFlow::Break(b) = f(&window) {
+                return (ControlFlow::Break(b), processed);
+            }
+        }
+        (ControlFlow::Continue(()), processed)
+    }
+
+    /// Tests whether `pred` holds for every window, stopping at the first window it doesn't
+    /// hold for. Like `Iterator::all`, but `pred` borrows the window, so no `Window` has to be
+    /// moved out of the adaptor. True if no window is yielded.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let timestamps = [1, 3, 3, 7, 6, 9];
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let mut windows = timestamps.iter().cloned().sliding_windows(&mut storage);
+    ///
+    /// assert!(!windows.all_windows(|pair| pair.is_monotonic_increasing()));
+    /// // the adaptor stopped after the window [7, 6]
+    /// assert_eq!(windows.next().unwrap(), &[6, 9][..]);
+    /// ```
+    pub fn all_windows<F>(&mut self, mut pred: F) -> bool where F: FnMut(&Window<'a, I::Item>) -> bool {
+        let (flow, _) = self.try_for_each_window(|window| {
+            if pred(window) { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+        });
+        flow.is_continue()
+    }
+
+    /// Tests whether `pred` holds for any window, stopping at the first window it holds for.
+    /// False if no window is yielded, see [all_windows](#method.all_windows).
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    ///
+    /// assert!((0..10).sliding_windows(&mut storage).any_window(|w| w.iter().sum::<u32>() == 12));
+    /// assert!(!(0..10).sliding_windows(&mut storage).any_window(|w| w.iter().sum::<u32>() == 13));
+    /// ```
+    pub fn any_window<F>(&mut self, mut pred: F) -> bool where F: FnMut(&Window<'a, I::Item>) -> bool {
+        let (flow, _) = self.try_for_each_window(|window| {
+            if pred(window) { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+        flow.is_break()
+    }
+
+    /// Yields every window as an owned `Vec` in logical order, wrapped in a
+    /// `std::iter::Peekable`.
+    ///
+    /// `Window`s borrow the storage and can't outlive the next call to `next()`, so the
+    /// adaptor itself can't be made peekable. This is the interop path for code which insists
+    /// on a `Peekable`, at the cost of cloning every window.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(2);
+    /// let mut windows = (0..4).sliding_windows(&mut storage).cloned_peekable();
+    ///
+    /// assert_eq!(windows.peek(), Some(&vec![0, 1]));
+    /// assert_eq!(windows.next_if(|w| w[0] == 0), Some(vec![0, 1]));
+    /// assert_eq!(windows.collect::<Vec<_>>(), [[1, 2], [2, 3]]);
+    /// ```
+    pub fn cloned_peekable(self) -> Peekable<ClonedWindows<'a, I, S>> where I::Item: Clone {
+        ClonedWindows { inner: self }.peekable()
+    }
+
+    // the same adaptor reading the elements of `iter` through `f(iter)`
+    pub(crate) fn map_iter<J, F>(self, f: F) -> Adaptor<'a, J, S>
+        where J: Iterator<Item = I::Item>, F: FnOnce(I) -> J
+    {
+        Adaptor {
+            iter: f(self.iter),
+            done: self.done,
+            storage: self.storage,
+            _attachment: self._attachment,
+            decay: self.decay,
+            on_short: self.on_short,
+            min_fill: self.min_fill,
+            gap: self.gap,
+            pushed: self.pushed,
+            yielded: self.yielded,
+            emit_every: self.emit_every,
+            skipped: self.skipped,
+            partial_suffix: self.partial_suffix,
+            last_fill: self.last_fill,
+            suffix: self.suffix,
+        }
+    }
+
+    pub(crate) fn window_size(&self) -> usize {
+        self.storage.window_size()
+    }
+
+    pub(crate) fn source(&self) -> &I {
+        &self.iter
+    }
+
+    pub(crate) fn source_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    pub(crate) fn into_source(self) -> I {
+        self.iter
+    }
+
+    // clones of the buffered elements, oldest first
+    pub(crate) fn buffered(&self) -> Vec<I::Item> where I::Item: Clone {
+        if self.pushed == 0 {
+            return Vec::new();
+        }
+        let window = self.storage.new_window();
+        window.iter().cloned().collect()
+    }
+}
+
+impl<'a, I: Iterator> Adaptor<'a, I> {
+    /// Creates a new Adaptor over a shared storage, e.g. one behind an `Rc`.
+    ///
+    /// Only one such adaptor can use a storage at a time, the storage is released when the
+    /// adaptor is dropped.
+    ///
+    /// ```
+    /// use sliding_windows::{Adaptor, Storage};
+    /// use std::rc::Rc;
+    ///
+    /// let storage: Rc<Storage<u32>> = Rc::new(Storage::new(3));
+    /// let shared = Rc::clone(&storage);
+    ///
+    /// let first = Adaptor::from_shared(0..5, &storage).unwrap();
+    /// assert!(Adaptor::from_shared(0..5, &shared).is_err());
+    ///
+    /// drop(first);
+    /// assert_eq!(Adaptor::from_shared(0..5, &shared).unwrap().count(), 3);
+    /// ```
+    ///
+    /// # Errors:
+    ///
+    /// Returns `StorageInUse` if another adaptor created by this method uses the storage.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the storage is poisoned, see [Storage::is_poisoned](struct.Storage.html#method.is_poisoned).
+    pub fn from_shared(iter: I, storage: &'a Storage<I::Item>) -> Result<Adaptor<'a, I>, StorageInUse> {
+        storage.assert_not_poisoned();
+        let attachment = storage.attach()?;
+        Ok(Adaptor::with_storage(iter, storage, Some(attachment)))
+    }
+
+    /// Tears down the adaptor, returning the buffered elements (oldest first) and the wrapped
+    /// iterator.
+    ///
+    /// The buffered elements are the ones of the last window, so together with the returned
+    /// iterator no element is lost when stopping early, e.g. on `iter.by_ref()`.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut windows = (0..10).sliding_windows(&mut storage);
+    /// windows.next();
+    /// windows.next();
+    ///
+    /// let (buffered, rest) = windows.stop();
+    /// assert_eq!(buffered, [1, 2, 3]);
+    /// assert_eq!(rest.start, 4);
+    /// ```
+    ///
+    /// # Panics:
+    ///
+    /// Panics if a `Window` of this adaptor is still alive.
+    pub fn stop(self) -> (Vec<I::Item>, I) {
+        let buffered = self.storage.take_buffered();
+        (buffered, self.iter)
+    }
+}
+
+/// The number of full windows of `window_size` elements over `input_len` elements, starting a
+/// window every `step` elements.
+///
+/// This is the number of windows an `Adaptor` yields once the first window is full, with a
+/// `step` of 1 or of [emit_every](struct.Adaptor.html#method.emit_every). It is a `const fn`,
+/// so it can size arrays at compile time:
+///
+/// ```
+/// use sliding_windows::{windows_len, IterExt, Storage};
+///
+/// const INPUT: [u32; 6] = [1, 2, 3, 4, 5, 6];
+/// let mut sums = [0; windows_len(INPUT.len(), 3, 1)];
+///
+/// let mut storage: Storage<u32> = Storage::new(3);
+/// for (sum, window) in sums.iter_mut().zip(INPUT.iter().cloned().sliding_windows(&mut storage)) {
+///     *sum = window.iter().sum();
+/// }
+/// assert_eq!(sums, [6, 9, 12, 15]);
+/// assert_eq!(windows_len(6, 3, 2), 2);
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `step` is zero.
+pub const fn windows_len(input_len: usize, window_size: usize, step: usize) -> usize {
+    assert!(step > 0, "step must be positive");
+    if window_size == 0 || input_len < window_size {
+        0
+    } else {
+        (input_len - window_size) / step + 1
     }
 }
 
-impl<'a, I: Iterator> Iterator for Adaptor<'a, I> {
+/// Yields sliding windows over the concatenation of several iterators.
+///
+/// Windows near the seam of two iterators span both of them, elements are taken in the order
+/// of `iters`. A short input only yields a partial window if all iterators together are
+/// shorter than the window, see [OnShort](enum.OnShort.html).
+///
+/// ```
+/// use sliding_windows::{sliding_windows_chain, Storage};
+///
+/// let mut storage: Storage<u32> = Storage::new(3);
+/// let sources = vec![vec![0, 1], vec![], vec![2, 3]];
+/// let windows: Vec<Vec<u32>> = sliding_windows_chain(sources, &mut storage)
+///     .map(|window| window.iter().cloned().collect())
+///     .collect();
+///
+/// assert_eq!(windows, [[0, 1, 2], [1, 2, 3]]);
+/// ```
+pub fn sliding_windows_chain<II>(iters: II, storage: &mut Storage<<II::Item as IntoIterator>::Item>)
+    -> Adaptor<'_, Flatten<II::IntoIter>>
+    where II: IntoIterator, II::Item: IntoIterator
+{
+    Adaptor::new(iters.into_iter().flatten(), storage)
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Iterator for Adaptor<'a, I, S> {
     type Item = Window<'a, I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.done || self.storage.window_size == 0 {
+        if (self.done && self.suffix.is_none()) || self.storage.window_size() == 0 {
             return None;
         }
-        self.done = true;
+        // usage errors are raised before the guard, they leave the storage intact
+        self.storage.assert_not_poisoned();
+        self.storage.assert_no_window();
+        let guard = PoisonGuard::new(self.storage);
+        if self.suffix.is_some() {
+            return self.next_suffix();
+        }
+        // elements buffered by warmup make up a partial window even if the input ended
+        self.done = self.pushed == 0;
+        let mut ready = false;
+        let min_fill = self.min_fill.unwrap_or(usize::MAX);
 
         for elt in &mut self.iter {
             self.done = false;
-            if self.storage.push(elt) {
+            if let Some(ref mut gap) = self.gap {
+                if gap(&elt) {
+                    self.storage.clear();
+                    self.pushed = 0;
+                    self.yielded = false;
+                    self.skipped = 0;
+                }
+            }
+            self.pushed = self.pushed.saturating_add(1);
+            if self.yielded && self.skipped + self.storage.window_size() < self.emit_every {
+                // the element is evicted before the next window is yielded
+                self.skipped += 1;
+                continue;
+            }
+            if self.storage.push(elt) || self.pushed >= min_fill {
+                if self.yielded && self.skipped + 1 < self.emit_every {
+                    // a dropped window still slid
+                    self.skipped += 1;
+                    if let Some(ref mut decay) = self.decay {
+                        decay_retained(&mut self.storage.new_window(), decay);
+                    }
+                    continue;
+                }
+                ready = true;
                 break;
             }
         }
 
+        if !ready && self.yielded {
+            // the input ended, possibly while dropping windows
+            self.done = true;
+            if self.partial_suffix {
+                // the next window would start `emit_every` elements after the last one
+                self.suffix = Some((self.last_fill + self.skipped).saturating_sub(self.emit_every));
+                return self.next_suffix();
+            }
+            return None;
+        }
+
+        if !ready && !self.yielded {
+            // the input ended before the first window was full
+            match self.on_short {
+                OnShort::YieldPartial if self.min_fill.is_none() => {},
+                OnShort::YieldPartial | OnShort::Empty => {
+                    self.done = true;
+                    return None;
+                },
+                OnShort::Error => {
+                    drop(guard);
+                    panic!("input ended after {} elements, before the first window of size {} was full",
+                           self.pushed, self.storage.window_size())
+                },
+            }
+        }
+
         if !self.done {
             // return new window
-            Some(self.storage.new_window())
+            let mut window = self.storage.new_window();
+            if let Some(ref mut decay) = self.decay {
+                if self.yielded {
+                    decay_retained(&mut window, decay);
+                }
+            }
+            self.yielded = true;
+            self.skipped = 0;
+            self.last_fill = self.pushed.min(self.storage.window_size());
+            Some(window)
         } else {
             None
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.storage.window_size;
-        let (mut lower, mut upper): (usize, Option<usize>) = self.iter.size_hint();
+        let (lower, upper) = self.full_size_hint();
+        if !self.partial_suffix || self.storage.window_size() == 0 {
+            return (lower, upper);
+        }
+        if let Some(fill) = self.suffix {
+            let trailing = fill.div_ceil(self.emit_every);
+            return (trailing, Some(trailing));
+        }
+        if self.done {
+            return (0, Some(0));
+        }
+        // the next window after the last full one starts in the last `window_size - 1` elements
+        let trailing = (self.storage.window_size() - 1).div_ceil(self.emit_every);
+        (lower, upper.and_then(|upper| upper.checked_add(trailing)))
+    }
+}
 
-        if size == 0 {
-            return (0, None);
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> {
+    // the size hint without the trailing windows of `with_partial_suffix`
+    fn full_size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.storage.window_size();
+        let (lower, upper): (usize, Option<usize>) = self.iter.size_hint();
+
+        if size == 0 || self.done {
+            return (0, Some(0));
+        }
+
+        // any element might start over after a gap
+        if self.gap.is_some() {
+            return (0, upper);
+        }
+
+        // once a window was yielded, every element slides the window
+        let k = self.emit_every;
+        if self.yielded {
+            let emitted = |slides: usize| slides.saturating_add(self.skipped) / k;
+            return (emitted(lower), upper.map(emitted));
         }
 
-        lower = match lower {
+        let first = self.min_fill.unwrap_or(size);
+        let partial = match (self.min_fill, self.on_short) {
+            (None, OnShort::YieldPartial) => 1,
+            _ => 0,
+        };
+        // the first window is yielded, then every k-th
+        let windows = |len: usize| match len {
             0 => 0,
-            x if x >= size => x - size + 1,
-            _ => 1
+            x if x >= first => windows_len(x, first, k),
+            _ => partial,
         };
+        // elements buffered by warmup count towards the first window
+        let buffered = |len: usize| windows(len.saturating_add(self.pushed));
 
-        upper = upper.map(|upper|
-            match upper {
-                0 => 0,
-                x if x >= size => x - size + 1,
-                _ => 1
-            }
-        );
+        (buffered(lower), upper.map(buffered))
+    }
+}
+
+/// Owned windows, see [Adaptor::cloned_peekable](struct.Adaptor.html#method.cloned_peekable).
+pub struct ClonedWindows<'a, I: Iterator, S: 'a = Storage<<I as Iterator>::Item>> where <I as Iterator>::Item: 'a {
+    inner: Adaptor<'a, I, S>,
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Iterator for ClonedWindows<'a, I, S> where I::Item: Clone {
+    type Item = Vec<I::Item>;
 
-        (lower, upper)
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        self.inner.next().map(|window| window.iter().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
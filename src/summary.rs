@@ -0,0 +1,152 @@
+use std::cell::Cell;
+use std::ops::Deref;
+
+use num::WindowNum;
+use sliding_windows::Window;
+
+#[derive(Clone, Copy)]
+struct Summary<T> {
+    sum: T,
+    min: Option<T>,
+    max: Option<T>,
+}
+
+/// A `Window` which computes its sum, minimum and maximum on first use and caches them.
+///
+/// This lets several consumers of one window share the aggregates without recomputing them.
+/// The cache lives exactly as long as the window, so it can't outlive the next slide.
+/// The wrapper only gives shared access to the window, which keeps the cache valid.
+///
+/// # Example:
+///
+/// ```
+/// use sliding_windows::{IterExt, Storage, SummarizedWindow};
+///
+/// let mut storage: Storage<u32> = Storage::new(3);
+/// for window in (0..5).sliding_windows(&mut storage).map(SummarizedWindow::new) {
+///     // computed once, the second call is cached
+///     let range = window.max().unwrap() - window.min().unwrap();
+///     assert_eq!(range, 2);
+///     assert_eq!(window.sum(), 3 * window.min().unwrap() + 3);
+/// }
+/// ```
+pub struct SummarizedWindow<'a, T: 'a> {
+    window: Window<'a, T>,
+    summary: Cell<Option<Summary<T>>>,
+}
+
+impl<'a, T: WindowNum> SummarizedWindow<'a, T> {
+    pub fn new(window: Window<'a, T>) -> SummarizedWindow<'a, T> {
+        SummarizedWindow {
+            window,
+            summary: Cell::new(None),
+        }
+    }
+
+    /// The sum of the elements, zero for an empty window.
+    pub fn sum(&self) -> T {
+        self.summary().sum
+    }
+
+    /// The smallest element, `None` for an empty window.
+    pub fn min(&self) -> Option<T> {
+        self.summary().min
+    }
+
+    /// The largest element, `None` for an empty window.
+    pub fn max(&self) -> Option<T> {
+        self.summary().max
+    }
+
+    /// Returns the wrapped window, dropping the cache.
+    pub fn into_inner(self) -> Window<'a, T> {
+        self.window
+    }
+
+    fn summary(&self) -> Summary<T> {
+        if let Some(summary) = self.summary.get() {
+            return summary;
+        }
+
+        let mut summary = Summary { sum: T::zero(), min: None, max: None };
+        for &x in &self.window {
+            summary.sum = summary.sum + x;
+            if summary.min.is_none_or(|min| x < min) {
+                summary.min = Some(x);
+            }
+            if summary.max.is_none_or(|max| x > max) {
+                summary.max = Some(x);
+            }
+        }
+
+        self.summary.set(Some(summary));
+        summary
+    }
+}
+
+impl<'a, T> Deref for SummarizedWindow<'a, T> {
+    type Target = Window<'a, T>;
+
+    fn deref(&self) -> &Window<'a, T> {
+        &self.window
+    }
+}
+
+/// Summary statistics of a window, see [Window::stats](struct.Window.html#method.stats).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats<T> {
+    /// The number of elements.
+    pub count: usize,
+    /// The arithmetic mean, `NaN` for an empty window.
+    pub mean: f64,
+    /// The smallest element, `None` for an empty window.
+    pub min: Option<T>,
+    /// The largest element, `None` for an empty window.
+    pub max: Option<T>,
+    /// The sample variance, `NaN` for windows with less than two elements.
+    pub variance: f64,
+}
+
+impl<'a, T: WindowNum> Window<'a, T> {
+    /// Computes the count, mean, minimum, maximum and variance of the elements in a single
+    /// pass, for a quick summary without composing several rolling adaptors.
+    ///
+    /// The mean and the variance are accumulated as `f64` with Welford's algorithm.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(4);
+    /// let stats = (1..6).sliding_windows(&mut storage).map(|window| window.stats()).last().unwrap();
+    ///
+    /// assert_eq!((stats.count, stats.mean, stats.min, stats.max), (4, 3.5, Some(2), Some(5)));
+    /// assert!((stats.variance - 5.0 / 3.0).abs() < 1e-12);
+    /// ```
+    pub fn stats(&self) -> WindowStats<T> {
+        let mut stats = WindowStats { count: 0, mean: 0.0, min: None, max: None, variance: 0.0 };
+        // the sum of squared differences from the mean
+        let mut m2 = 0.0;
+
+        let (older, newer) = self.iter().as_slice_pair();
+        for &x in older.iter().chain(newer) {
+            stats.count += 1;
+            let value = x.to_f64();
+            let delta = value - stats.mean;
+            stats.mean += delta / stats.count as f64;
+            m2 += delta * (value - stats.mean);
+
+            if stats.min.is_none_or(|min| x < min) {
+                stats.min = Some(x);
+            }
+            if stats.max.is_none_or(|max| x > max) {
+                stats.max = Some(x);
+            }
+        }
+
+        if stats.count == 0 {
+            stats.mean = f64::NAN;
+        }
+        stats.variance = if stats.count < 2 { f64::NAN } else { m2 / (stats.count - 1) as f64 };
+        stats
+    }
+}
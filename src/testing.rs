@@ -0,0 +1,163 @@
+//! Helpers for testing code which consumes `Window`s, available with the `testing` feature.
+
+#[cfg(feature = "rand")]
+use rand::rngs::StdRng;
+#[cfg(feature = "rand")]
+use rand::{Rng, SeedableRng};
+
+use sliding_windows::{Adaptor, Storage, Window};
+use std::fmt::Debug;
+use std::slice;
+
+/// Yields one `Window` per slice of `windows`, holding clones of its elements.
+///
+/// This allows testing functions taking `Window`s with hand written windows, which may
+/// differ in length, instead of deriving them from a stream. As with
+/// [Adaptor](../struct.Adaptor.html), every window has to be dropped before the next one
+/// is requested. The window size of `storage` is changed to the length of the longest slice.
+pub fn windows_from<'i, 's, T: Clone>(windows: &'i [&'i [T]], storage: &'s mut Storage<T>) -> WindowsFrom<'i, 's, T> {
+    let window_size = windows.iter().map(|w| w.len()).max().unwrap_or(0);
+    storage.set_window_size(window_size);
+
+    WindowsFrom {
+        windows: windows.iter(),
+        storage,
+    }
+}
+
+/// See [windows_from](fn.windows_from.html) for more information.
+pub struct WindowsFrom<'i, 's, T: 's> {
+    windows: slice::Iter<'i, &'i [T]>,
+    storage: &'s Storage<T>,
+}
+
+impl<'i, 's, T: Clone> Iterator for WindowsFrom<'i, 's, T> {
+    type Item = Window<'s, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elts = self.windows.next()?;
+        self.storage.refill(elts);
+        Some(self.storage.new_window())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<'i, 's, T: Clone> ExactSizeIterator for WindowsFrom<'i, 's, T> {}
+
+/// Runs `f` on every window of `data` twice, once on the `Window`s of an `Adaptor` and once
+/// on subslices of `data`, and asserts that both return the same results.
+/// Returns the number of compared windows.
+///
+/// Like the `Adaptor`, the reference yields a single partial window if `data` is shorter
+/// than `window_size`, and nothing if `window_size` is 0.
+///
+/// # Panics:
+///
+/// Panics if any result or the number of windows differs.
+pub fn assert_same_as_slice_windows<T, R, F>(data: &[T], window_size: usize, mut f: F) -> usize
+    where T: Clone, R: PartialEq + Debug, F: FnMut(&mut dyn Iterator<Item = &T>) -> R
+{
+    let mut storage = Storage::new(window_size);
+    let actual: Vec<R> = Adaptor::new(data.iter().cloned(), &mut storage)
+        .map(|window| f(&mut window.iter()))
+        .collect();
+
+    let expected: Vec<R> = if window_size == 0 || data.is_empty() {
+        Vec::new()
+    } else if data.len() < window_size {
+        vec![f(&mut data.iter())]
+    } else {
+        data.windows(window_size).map(|window| f(&mut window.iter())).collect()
+    };
+
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        assert_eq!(actual, expected, "window {} differs from the reference (window size {})", i, window_size);
+    }
+    assert_eq!(actual.len(), expected.len(), "number of windows differs from the reference (window size {})", window_size);
+    actual.len()
+}
+
+/// Yields an infinite stream of uniformly distributed values in `[low, high)`, which is the
+/// same for every run with the same `seed`. Requires the `rand` feature.
+///
+/// # Panics:
+///
+/// Panics if `low` is not smaller than `high`.
+#[cfg(feature = "rand")]
+pub fn seeded_uniform(seed: u64, low: f64, high: f64) -> SeededStream {
+    assert!(low < high, "low {} must be smaller than high {}", low, high);
+    SeededStream { rng: StdRng::seed_from_u64(seed), distribution: Distribution::Uniform { low, high } }
+}
+
+/// Yields an infinite stream of normally distributed values, which is the same for every run
+/// with the same `seed`. Requires the `rand` feature.
+///
+/// This allows deterministic statistical tests of windowed computations, e.g. that a
+/// rolling statistic converges to the parameters of the distribution:
+///
+/// ```
+/// # extern crate sliding_windows;
+/// use sliding_windows::IterExt;
+/// use sliding_windows::testing::seeded_normal;
+///
+/// # fn main() {
+/// let readings: Vec<f64> = seeded_normal(42, 20.0, 2.0).take(20_000).collect();
+///
+/// let means: Vec<f64> = readings.iter().cloned().rolling_mean(10_000).collect();
+/// assert!(means.iter().all(|mean| (mean - 20.0).abs() < 0.1));
+///
+/// let variances: Vec<f64> = readings.iter().cloned()
+///     .rolling_cov(readings.iter().cloned(), 10_000)
+///     .collect();
+/// assert!(variances.iter().all(|variance| (variance - 4.0).abs() < 0.25));
+/// # }
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `std_dev` is negative or not finite.
+#[cfg(feature = "rand")]
+pub fn seeded_normal(seed: u64, mean: f64, std_dev: f64) -> SeededStream {
+    assert!(std_dev >= 0.0 && std_dev.is_finite(), "std_dev {} must be finite and not negative", std_dev);
+    SeededStream { rng: StdRng::seed_from_u64(seed), distribution: Distribution::Normal { mean, std_dev } }
+}
+
+#[cfg(feature = "rand")]
+enum Distribution {
+    Uniform { low: f64, high: f64 },
+    Normal { mean: f64, std_dev: f64 },
+}
+
+/// See [seeded_uniform](fn.seeded_uniform.html) and [seeded_normal](fn.seeded_normal.html)
+/// for more information.
+#[cfg(feature = "rand")]
+pub struct SeededStream {
+    rng: StdRng,
+    distribution: Distribution,
+}
+
+#[cfg(feature = "rand")]
+impl Iterator for SeededStream {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let x = match self.distribution {
+            Distribution::Uniform { low, high } => low + (high - low) * self.rng.random::<f64>(),
+            Distribution::Normal { mean, std_dev } => {
+                // Box-Muller, 1 - u is in (0, 1] so its logarithm is finite
+                let u: f64 = self.rng.random();
+                let v: f64 = self.rng.random();
+                let z = (-2.0 * (1.0 - u).ln()).sqrt() * (2.0 * ::std::f64::consts::PI * v).cos();
+                mean + std_dev * z
+            },
+        };
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
@@ -1,6 +1,8 @@
 use super::*;
+use std::cell::Cell;
 
 #[test]
+#[allow(clippy::map_clone)]
 fn sliding_windows_1() {
     fn test_window_correctness_with_storage(mut storage: Storage<u32>) {
         let expected: &[&[u32]] = &[&[0,1,2], &[1,2,3], &[2,3,4]];
@@ -24,6 +26,7 @@ fn sliding_windows_1() {
 }
 
 #[test]
+#[allow(unused_mut)]
 fn sliding_windows_2() {
     let it = 0..5;
     let mut storage: Storage<u32> = Storage::new(3);
@@ -97,3 +100,2508 @@ fn sliding_windows_6() {
     let storage2: Vec<u32> = storage2.into();
     assert_eq!(storage2.capacity(), 20);
 }
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} is not close to {}", a, b);
+}
+
+#[test]
+fn rolling_corr_1() {
+    let a = [1.0, 2.0, 4.0, 3.0, 5.0, 5.0];
+    let b = [2.0, 1.0, 7.0, 3.0, 8.0, 1.0];
+
+    let corr: Vec<f64> = a.iter().cloned().rolling_corr(b.iter().cloned(), 4).collect();
+    let cov: Vec<f64> = a.iter().cloned().rolling_cov(b.iter().cloned(), 4).collect();
+    assert_eq!(corr.len(), 3);
+    assert_eq!(cov.len(), 3);
+
+    // compare against the textbook formulas evaluated on every window separately
+    for (i, (x, y)) in a.windows(4).zip(b.windows(4)).enumerate() {
+        let mean_x = x.iter().sum::<f64>() / 4.0;
+        let mean_y = y.iter().sum::<f64>() / 4.0;
+        let sxy: f64 = x.iter().zip(y).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let sxx: f64 = x.iter().map(|x| (x - mean_x) * (x - mean_x)).sum();
+        let syy: f64 = y.iter().map(|y| (y - mean_y) * (y - mean_y)).sum();
+
+        assert_close(cov[i], sxy / 3.0);
+        assert_close(corr[i], sxy / (sxx * syy).sqrt());
+    }
+}
+
+#[test]
+fn rolling_corr_2() {
+    // the shorter stream ends iteration, constant windows are NaN
+    let corr: Vec<f64> = (0u8..10).rolling_corr(vec![1u8; 4], 3).collect();
+    assert_eq!(corr.len(), 2);
+    assert!(corr.iter().all(|x| x.is_nan()));
+
+    assert_eq!((0u8..10).rolling_corr(0u8..10, 0).count(), 0);
+
+    assert_eq!((0u8..10).rolling_corr(vec![1u8; 4], 3).size_hint(), (2, Some(2)));
+    assert_eq!((0u8..10).rolling_cov(0u8..10, 3).size_hint(), (8, Some(8)));
+    assert_eq!((0u8..10).rolling_cov(0u8..10, 0).size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn rolling_corr_3() {
+    // a large common offset must not cancel the small spread of the values
+    let x: Vec<f64> = (0..2000).map(|i| 1e8 + ((i * 7919) % 13) as f64 / 10.0).collect();
+    let y: Vec<f64> = x.iter().map(|x| 2.0 * x + 5e8).collect();
+
+    let var: Vec<f64> = x.iter().cloned().rolling_cov(x.iter().cloned(), 50).collect();
+    let corr: Vec<f64> = x.iter().cloned().rolling_corr(y.iter().cloned(), 50).collect();
+    assert_eq!(var.len(), 1951);
+
+    for (i, w) in x.windows(50).enumerate() {
+        let mean = w.iter().map(|x| x - 1e8).sum::<f64>() / 50.0;
+        let expected = w.iter().map(|x| (x - 1e8 - mean).powi(2)).sum::<f64>() / 49.0;
+        assert!((var[i] - expected).abs() < 1e-6 * expected, "{} is not close to {}", var[i], expected);
+        assert!((corr[i] - 1.0).abs() < 1e-6, "{} is not close to 1", corr[i]);
+    }
+}
+
+#[test]
+fn rolling_trend_1() {
+    let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+    let trends: Vec<Trend> = data.iter().cloned().rolling_trend(5).collect();
+    assert_eq!(trends.len(), 4);
+
+    for (trend, y) in trends.iter().zip(data.windows(5)) {
+        let mean_x = 2.0;
+        let mean_y = y.iter().sum::<f64>() / 5.0;
+        let sxy: f64 = y.iter().enumerate().map(|(x, y)| (x as f64 - mean_x) * (y - mean_y)).sum();
+        let sxx: f64 = (0..5).map(|x| (x as f64 - mean_x) * (x as f64 - mean_x)).sum();
+        let syy: f64 = y.iter().map(|y| (y - mean_y) * (y - mean_y)).sum();
+
+        assert_close(trend.slope, sxy / sxx);
+        assert_close(trend.intercept, mean_y - sxy / sxx * mean_x);
+        assert_close(trend.r_squared, sxy * sxy / (sxx * syy));
+    }
+}
+
+#[test]
+fn rolling_trend_2() {
+    let trends: Vec<Trend> = (0u32..6).map(|x| 2 * x + 1).rolling_trend(3).collect();
+    assert_eq!(trends.len(), 4);
+    for (i, trend) in trends.iter().enumerate() {
+        assert_close(trend.slope, 2.0);
+        assert_close(trend.intercept, 2.0 * i as f64 + 1.0);
+        assert_close(trend.r_squared, 1.0);
+    }
+
+    assert_eq!((0u32..6).rolling_trend(0).count(), 0);
+    assert_eq!((0u32..2).rolling_trend(3).count(), 0);
+
+    let mut trends = (0u32..6).rolling_trend(3);
+    assert_eq!(trends.size_hint(), (4, Some(4)));
+    trends.next();
+    assert_eq!(trends.size_hint(), (3, Some(3)));
+}
+
+#[test]
+fn rolling_trend_3() {
+    // a large offset must not cancel the spread of y
+    let trends: Vec<Trend> = (0u32..5000).map(|i| 1e9 + 0.5 * i as f64).rolling_trend(20).collect();
+    assert_eq!(trends.len(), 4981);
+    for (i, trend) in trends.iter().enumerate() {
+        assert_close(trend.slope, 0.5);
+        assert_close(trend.intercept, 1e9 + 0.5 * i as f64);
+        assert_close(trend.r_squared, 1.0);
+    }
+}
+
+#[test]
+fn detect_1() {
+    use detect::{ChangePoint, Detector};
+
+    let data = [1.0, 2.0, 1.0, 2.0, 1.0, -9.0, 2.0, 1.0];
+    let detector = Detector::ZScore { window_size: 4, threshold: 3.0 };
+    let points: Vec<ChangePoint> = data.iter().cloned().detect(detector).collect();
+
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].index, 5);
+    // the four preceding elements have mean 1.5 and sample variance 1/3
+    assert_close(points[0].score, -10.5 / (1.0f64 / 3.0).sqrt());
+
+    // a constant window doesn't flag an equal element
+    let detector = Detector::ZScore { window_size: 3, threshold: 1.0 };
+    assert_eq!([5u8; 6].iter().cloned().detect(detector).count(), 0);
+}
+
+#[test]
+fn detect_2() {
+    use detect::Detector;
+
+    // a small persistent shift is invisible to the z-score but accumulates in the CUSUM
+    let data: Vec<f64> = (0..40).map(|i| {
+        let noise = if i % 2 == 0 { 1.0 } else { -1.0 };
+        if i < 20 { noise } else { noise + 0.8 }
+    }).collect();
+
+    let zscore = Detector::ZScore { window_size: 10, threshold: 3.0 };
+    assert_eq!(data.iter().cloned().detect(zscore).count(), 0);
+
+    let cusum = Detector::Cusum { window_size: 10, drift: 0.25, threshold: 2.0 };
+    let points: Vec<_> = data.iter().cloned().detect(cusum).collect();
+    assert!(!points.is_empty());
+    assert!(points[0].index >= 20);
+    assert!(points[0].score > 2.0);
+}
+
+#[test]
+fn detect_3() {
+    use detect::Detector;
+
+    // stationary noise on a large offset, only the spike is flagged
+    let mut data: Vec<f64> = (0..5000).map(|i| 1e8 + if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+    data[3000] += 20.0;
+    let detector = Detector::ZScore { window_size: 50, threshold: 4.0 };
+    let points: Vec<_> = data.iter().cloned().detect(detector).collect();
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].index, 3000);
+    assert!(points[0].score.is_finite());
+
+    // a single element has no spread to compare against
+    let detector = Detector::ZScore { window_size: 1, threshold: 1.0 };
+    assert_eq!([1.0, 5.0, 2.0].iter().cloned().detect(detector).count(), 0);
+
+    assert_eq!(data.iter().cloned().detect(detector).size_hint(), (0, Some(5000)));
+}
+
+#[test]
+fn timestamp_skew_1() {
+    use std::time::{Duration, Instant};
+
+    // equal timestamps and jumps up to the bound are fine
+    let timestamps = [5u32, 5, 7, 3, 3, 13, 14, 2];
+    let skews: Vec<Skew<u32>> = timestamps.iter().cloned().timestamp_skew(10).collect();
+    assert_eq!(skews, [Skew::Backwards { index: 3, delta: 4 }, Skew::Backwards { index: 7, delta: 12 }]);
+
+    let skews: Vec<Skew<u32>> = timestamps.iter().cloned().timestamp_skew(1).collect();
+    assert_eq!(skews.iter().map(|s| s.index()).collect::<Vec<_>>(), [2, 3, 5, 7]);
+    assert_eq!(skews[2], Skew::Jump { index: 5, delta: 10 });
+
+    // instants, whose difference is a Duration
+    let start = Instant::now();
+    let instants = [start, start + Duration::from_secs(1), start + Duration::from_secs(5)];
+    let skews: Vec<_> = instants.iter().cloned().timestamp_skew(Duration::from_secs(2)).collect();
+    assert_eq!(skews, [Skew::Jump { index: 2, delta: Duration::from_secs(4) }]);
+
+    assert_eq!([1.0f64].iter().cloned().timestamp_skew(0.0).count(), 0);
+}
+
+#[test]
+fn debounce_1() {
+    // a quorum of 2 out of 3 flips on the second of two close hits
+    let data = [0, 1, 0, 1, 0, 0, 1, 1, 0];
+    let state: Vec<bool> = data.iter().debounce(3, 2, |&&x| x == 1).collect();
+    assert_eq!(state, [false, false, false, true, false, false, false, true, true]);
+}
+
+#[test]
+#[should_panic]
+fn debounce_2() {
+    let _ = (0..5).debounce(3, 4, |_| true);
+}
+
+#[test]
+fn rolling_majority_1() {
+    let labels = [1, 2, 2, 1, 1, 3, 3];
+
+    let recent: Vec<u8> = labels.iter().cloned().rolling_majority(4, TiePolicy::MostRecent).collect();
+    assert_eq!(recent, [1, 1, 1, 3]);
+
+    // [1,2,2,1] is a tie, 2 was yielded before
+    let labels = [2, 2, 1, 2, 1, 1, 1];
+    let recent: Vec<u8> = labels.iter().cloned().rolling_majority(4, TiePolicy::MostRecent).collect();
+    let keep: Vec<u8> = labels.iter().cloned().rolling_majority(4, TiePolicy::KeepPrevious).collect();
+    assert_eq!(recent, [2, 1, 1, 1]);
+    assert_eq!(keep, [2, 2, 1, 1]);
+
+    assert_eq!((0..3).rolling_majority(0, TiePolicy::MostRecent).count(), 0);
+}
+
+#[test]
+fn rolling_majority_2() {
+    // compare with counting every window, on labels from a linear congruential generator
+    let labels: Vec<u32> = (0u32..2000).scan(7u32, |x, _| {
+        *x = x.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        Some((*x >> 16) % 5)
+    }).collect();
+
+    for &window_size in &[1, 2, 7, 30] {
+        let recent: Vec<u32> = labels.iter().cloned().rolling_majority(window_size, TiePolicy::MostRecent).collect();
+        let expected: Vec<u32> = labels.windows(window_size).map(|w| {
+            let count = |x: &u32| w.iter().filter(|y| *y == x).count();
+            let max = w.iter().map(&count).max().unwrap();
+            *w.iter().rev().find(|x| count(x) == max).unwrap()
+        }).collect();
+        assert_eq!(recent, expected);
+    }
+
+    let majority = labels.iter().rolling_majority(30, TiePolicy::KeepPrevious);
+    assert_eq!(majority.size_hint(), (1971, Some(1971)));
+}
+
+#[test]
+fn decimate_1() {
+    let means: Vec<f64> = (0..9).map(f64::from).decimate(3, |c| c.iter().sum::<f64>() / 3.0).collect();
+    assert_eq!(means, [1.0, 4.0, 7.0]);
+
+    let iter = (0..8).decimate(3, |c| c[c.len() - 1]);
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.collect::<Vec<_>>(), [2, 5]);
+
+    assert_eq!((0..8).decimate(0, |c| c.len()).count(), 0);
+}
+
+#[test]
+fn lttb_1() {
+    // straight forward implementation working on the whole slice
+    fn reference(points: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+        let every = (points.len() - 2) as f64 / (threshold - 2) as f64;
+        let start = |bucket: usize| ((bucket as f64 * every) as usize + 1).min(points.len() - 1);
+        let mut selected = vec![points[0]];
+
+        for bucket in 0..threshold - 2 {
+            let next = if bucket + 1 == threshold - 2 {
+                &points[points.len() - 1..]
+            } else {
+                &points[start(bucket + 1)..start(bucket + 2)]
+            };
+            let avg_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+            let avg_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+            let a = selected[selected.len() - 1];
+
+            let end = if bucket + 1 == threshold - 2 { points.len() - 1 } else { start(bucket + 1) };
+            let mut best = (points[start(bucket)], -1.0);
+            for &p in &points[start(bucket)..end] {
+                let area = ((a.0 - avg_x) * (p.1 - a.1) - (a.0 - p.0) * (avg_y - a.1)).abs();
+                if area > best.1 {
+                    best = (p, area);
+                }
+            }
+            selected.push(best.0);
+        }
+
+        selected.push(points[points.len() - 1]);
+        selected
+    }
+
+    let points: Vec<(f64, f64)> = (0..101).map(|x| {
+        let x = x as f64;
+        (x, (x / 7.0).sin() * 10.0 + (x / 3.0).cos())
+    }).collect();
+
+    for &threshold in &[3, 4, 10, 33, 100] {
+        let iter = points.iter().cloned().lttb(threshold);
+        assert_eq!(iter.len(), threshold);
+        let output: Vec<(f64, f64)> = iter.collect();
+        assert_eq!(output, reference(&points, threshold));
+    }
+
+    // nothing to downsample
+    assert_eq!(points.iter().cloned().lttb(101).count(), 101);
+    assert_eq!(points.iter().cloned().lttb(2).count(), 101);
+    assert_eq!(Vec::<(f64, f64)>::new().into_iter().lttb(5).count(), 0);
+}
+
+#[test]
+fn resample_1() {
+    // downsampling keeps every third sample
+    let down: Vec<f64> = (0..10).map(f64::from).resample(3.0, 1.0, Interpolation::Linear).collect();
+    assert_eq!(down, [0.0, 3.0, 6.0, 9.0]);
+
+    // cubic interpolation reproduces quadratic functions inside of the input
+    let square: Vec<f64> = (0..8).map(|x| f64::from(x * x)).collect();
+    let up: Vec<f64> = square.iter().cloned().resample(2.0, 8.0, Interpolation::Cubic).collect();
+    assert_eq!(up.len(), 29);
+    for (k, &y) in up.iter().enumerate().skip(4).take(20) {
+        let x = k as f64 / 4.0;
+        assert_close(y, x * x);
+    }
+    // the samples themselves are hit exactly
+    for (k, &y) in up.iter().enumerate().step_by(4) {
+        assert_close(y, square[k / 4]);
+    }
+
+    assert_eq!(Vec::<f64>::new().into_iter().resample(1.0, 2.0, Interpolation::Cubic).count(), 0);
+}
+
+#[test]
+fn sliding_dft_1() {
+    let signal: Vec<f64> = (0..200).map(|n| {
+        let n = n as f64;
+        (n * 0.3).sin() + 0.5 * (n * 1.1).cos() + (n % 7.0)
+    }).collect();
+
+    for &(bin, size) in &[(0, 5), (1, 16), (3, 16), (5, 9)] {
+        let bins: Vec<Complex> = signal.iter().cloned().sliding_dft(bin, size).collect();
+        assert_eq!(bins.len(), signal.len() - size + 1);
+
+        for (value, window) in bins.iter().zip(signal.windows(size)) {
+            let mut expected = (0.0, 0.0);
+            for (m, x) in window.iter().enumerate() {
+                let phi = -2.0 * ::std::f64::consts::PI * (bin * m) as f64 / size as f64;
+                expected.0 += x * phi.cos();
+                expected.1 += x * phi.sin();
+            }
+            assert!((value.re - expected.0).abs() < 1e-8);
+            assert!((value.im - expected.1).abs() < 1e-8);
+        }
+    }
+
+    assert_eq!(signal.iter().cloned().sliding_dft(0, 0).count(), 0);
+}
+
+#[test]
+fn window_num_1() {
+    assert_eq!(WindowNum::to_f64(-3i64), -3.0);
+    assert_eq!(<u64 as WindowNum>::zero(), 0);
+    assert_eq!(WindowNum::to_f64(1.5f32), 1.5);
+
+    // i64 and usize don't implement Into<f64>, but are valid elements
+    let big: Vec<f64> = (0i64..5).map(|x| x * 1_000_000_000_000).rolling_corr(0usize..5, 3).collect();
+    assert_eq!(big.len(), 3);
+    for corr in big {
+        assert_close(corr, 1.0);
+    }
+}
+
+#[test]
+fn rolling_sum_1() {
+    // huge values passing through the window leave rounding errors behind
+    let data: Vec<f64> = (0..4000).map(|i| if i % 4 == 0 { 1e16 } else { (i % 7) as f64 + 0.5 }).collect();
+    let exact: Vec<f64> = data.windows(3).map(|w| {
+        // all values are multiples of 0.5, so the doubled sum is exact in integers
+        let doubled: i128 = w.iter().map(|x| (x * 2.0) as i128).sum();
+        doubled as f64 / 2.0
+    }).collect();
+
+    let naive: Vec<f64> = data.iter().cloned().rolling_sum(3).collect();
+    let compensated: Vec<f64> = data.iter().cloned().rolling_sum(3).summation(Summation::Compensated).collect();
+    let recomputed: Vec<f64> = data.iter().cloned().rolling_sum(3).recompute_every(1).collect();
+    assert_eq!(naive.len(), exact.len());
+
+    let max_error = |sums: &[f64]| sums.iter().zip(&exact)
+        .filter(|&(_, e)| *e < 1e15)
+        .map(|(s, e)| (s - e).abs())
+        .fold(0.0, f64::max);
+    assert!(max_error(&naive) > 0.0);
+    assert_eq!(max_error(&compensated), 0.0);
+    assert_eq!(max_error(&recomputed), 0.0);
+}
+
+#[test]
+fn rolling_mean_1() {
+    let means: Vec<f64> = (1u8..8).rolling_mean(4).summation(Summation::Compensated).recompute_every(2).collect();
+    assert_eq!(means, [2.5, 3.5, 4.5, 5.5]);
+    assert_eq!((1u8..8).rolling_mean(0).count(), 0);
+    assert_eq!((1u8..8).rolling_sum(8).count(), 0);
+
+    assert_eq!((1u8..8).rolling_sum(4).size_hint(), (4, Some(4)));
+    assert_eq!((1u8..8).rolling_mean(8).size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn rolling_exact_stats_1() {
+    assert_eq!(Ratio::new(6, -4), Ratio::new(-3, 2));
+    assert_eq!(Ratio::new(0, 5).denom(), 1);
+    assert_eq!(Ratio::new(-6, 4).to_string(), "-3/2");
+    assert_eq!(Ratio::new(8, 4).to_string(), "2");
+
+    // values whose float sums wouldn't be exact
+    let big = 1i64 << 60;
+    let data = [big, big + 1, big + 3, -big, 7];
+    let stats: Vec<ExactStats> = data.iter().cloned().rolling_exact_stats(3).collect();
+    assert_eq!(stats.len(), 3);
+
+    for (stats, window) in stats.iter().zip(data.windows(3)) {
+        let window: Vec<i128> = window.iter().map(|&x| x as i128).collect();
+        let sum: i128 = window.iter().sum();
+        let sum_sq: i128 = window.iter().map(|x| x * x).sum();
+
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.mean(), Ratio::new(sum, 3));
+        assert_eq!(stats.variance(), Ratio::new(3 * sum_sq - sum * sum, 9));
+    }
+    assert_eq!(stats[0].variance(), Ratio::new(14, 9));
+
+    let single: Vec<ExactStats> = (0u8..2).rolling_exact_stats(1).collect();
+    assert_eq!(single[1].mean(), Ratio::new(1, 1));
+    assert_eq!(single[1].sample_variance(), None);
+}
+
+#[test]
+#[should_panic(expected = "exact statistics overflowed i128")]
+fn rolling_exact_stats_2() {
+    let _ = [u64::MAX, u64::MAX].iter().cloned().rolling_exact_stats(2).count();
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow_1() {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int64Type;
+    use arrow_array::Array;
+
+    let mut storage: Storage<i64> = Storage::new(3);
+    let batch = (0..5).sliding_windows(&mut storage).collect_record_batch::<Int64Type>().unwrap();
+
+    assert_eq!(batch.num_rows(), 3);
+    assert_eq!(batch.schema().field(0).name(), "window");
+
+    let windows = batch.column(0).as_list::<i32>();
+    for (i, window) in windows.iter().enumerate() {
+        let window = window.unwrap();
+        let values: Vec<i64> = window.as_primitive::<Int64Type>().values().to_vec();
+        assert_eq!(values, [i as i64, i as i64 + 1, i as i64 + 2]);
+        assert_eq!(window.len(), 3);
+    }
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn write_windows_1() {
+    let mut storage: Storage<f64> = Storage::new(2);
+    let mut output = Vec::new();
+    let iter = [0.5, 1.0, 1.5].iter().cloned().sliding_windows(&mut storage);
+
+    assert_eq!(iter.write_windows(&mut output, WindowFormat::Csv).unwrap(), 2);
+    assert_eq!(String::from_utf8(output).unwrap(), "0.5,1.0\n1.0,1.5\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn write_windows_2() {
+    // the window is written in logical order, even though the storage wrapped around
+    let mut storage: Storage<&str> = Storage::new(2);
+    let mut windows = ["a", "b", "c"].iter().cloned().sliding_windows(&mut storage);
+    windows.next();
+
+    let mut output = Vec::new();
+    assert_eq!(windows.write_windows(&mut output, WindowFormat::JsonLines).unwrap(), 1);
+    assert_eq!(output, b"[\"b\",\"c\"]\n");
+}
+
+#[test]
+fn display_1() {
+    let mut storage: Storage<f32> = Storage::new(2);
+    let output: Vec<String> = [0.5, 1.0, 2.5].iter().cloned().sliding_windows(&mut storage)
+        .map(|w| format!("[{}] {:.1}", w.display_with("|"), w))
+        .collect();
+
+    assert_eq!(output, ["[0.5|1] 0.5 1.0", "[1|2.5] 1.0 2.5"]);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_1() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tracing::{span, Dispatch, Event, Metadata, Subscriber};
+
+    // counts the events emitted per level
+    struct Counter(Arc<[AtomicUsize; 2]>);
+
+    impl Subscriber for Counter {
+        fn enabled(&self, _: &Metadata) -> bool { true }
+        fn new_span(&self, _: &span::Attributes) -> span::Id { span::Id::from_u64(1) }
+        fn record(&self, _: &span::Id, _: &span::Record) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event) {
+            let i = if *event.metadata().level() == tracing::Level::ERROR { 1 } else { 0 };
+            self.0[i].fetch_add(1, Ordering::SeqCst);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let counts = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+    let dispatch = Dispatch::new(Counter(counts.clone()));
+
+    tracing::dispatcher::with_default(&dispatch, || {
+        let mut storage: Storage<u32> = Storage::new(3);
+        // 3 windows and 2 evictions
+        assert_eq!((0..5).sliding_windows(&mut storage).count(), 3);
+
+        let mut storage: Storage<u32> = Storage::new(3);
+        let mut iter = (0..5).sliding_windows(&mut storage);
+        let _a = iter.next();
+        let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| iter.next().is_some()));
+        assert!(result.is_err());
+    });
+
+    assert_eq!(counts[0].load(Ordering::SeqCst), 6);
+    assert_eq!(counts[1].load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[should_panic(expected = "(storage \"stage 2\", window size 3, 3/3 elements buffered, live window starts at element 1)")]
+fn debug_name_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    storage.set_debug_name("stage 2");
+    let mut windowed_iter = Adaptor::new(0..5, &mut storage);
+
+    windowed_iter.next();
+    let _b = windowed_iter.next();
+    let _c = windowed_iter.next();
+}
+
+#[test]
+fn into_vec_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    assert_eq!(Adaptor::new(0..7, &mut storage).count(), 5);
+
+    // a reused storage starts over in logical order
+    {
+        let output: Vec<Vec<u32>> = Adaptor::new(10..14, &mut storage).map(|w| w.iter().cloned().collect()).collect();
+        assert_eq!(output, [[10, 11, 12], [11, 12, 13]]);
+    }
+
+    let empty: Vec<u32> = Storage::from_vec(vec![1, 2, 3], 3).into();
+    assert!(empty.is_empty());
+    assert_eq!(storage.into_vec(), [11, 12, 13]);
+}
+
+#[test]
+fn storage_memory_1() {
+    #[derive(Default)]
+    struct Pipeline {
+        storage: Storage<u32>,
+    }
+
+    let mut pipeline = Pipeline::default();
+    assert_eq!(pipeline.storage.window_size(), 0);
+    assert_eq!(pipeline.storage.capacity(), 0);
+    assert_eq!(Adaptor::new(0..5, &mut pipeline.storage).count(), 0);
+
+    pipeline.storage.set_window_size(2);
+    assert_eq!(Adaptor::new(0..5, &mut pipeline.storage).count(), 4);
+
+    pipeline.storage.reserve(100);
+    assert!(pipeline.storage.capacity() >= 102);
+    pipeline.storage.shrink_to_fit();
+    assert!(pipeline.storage.capacity() >= 2 && pipeline.storage.capacity() < 102);
+    assert_eq!(pipeline.storage.into_vec(), [3, 4]);
+}
+
+#[test]
+fn heap_size_hint_1() {
+    let mut storage: Storage<u64> = Storage::new(4);
+    assert_eq!(storage.heap_size_hint(), storage.capacity() * 8);
+
+    storage.set_debug_name("keyed");
+    assert!(storage.heap_size_hint() >= 4 * 8 + 5);
+
+    let vecs: Storage<Vec<u8>> = Storage::new(0);
+    assert_eq!(vecs.heap_size_hint(), 0);
+    assert_eq!(vecs.heap_size_hint_with(|v| v.capacity()), 0);
+}
+
+#[test]
+fn keyed_windows_1() {
+    let mut evicted = Vec::new();
+    {
+        let mut windows = KeyedWindows::new(2).with_max_keys(2).on_evict(|k, elts| evicted.push((k, elts)));
+
+        assert!(windows.push('a', 1).is_none());
+        assert!(windows.push('b', 2).is_none());
+        assert_eq!(windows.push('a', 3).unwrap(), &[1, 3][..]);
+        // 'b' is the least recently used key
+        assert!(windows.push('c', 4).is_none());
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows.push('a', 5).unwrap(), &[3, 5][..]);
+        // now 'c' is
+        assert!(windows.push('b', 6).is_none());
+
+        assert_eq!(windows.remove(&'a'), Some(vec![3, 5]));
+        assert_eq!(windows.remove(&'a'), None);
+        assert_eq!(windows.len(), 1);
+    }
+    assert_eq!(evicted, [('b', vec![2]), ('c', vec![4])]);
+
+    let mut empty: KeyedWindows<u8, u8> = KeyedWindows::new(0);
+    assert!(empty.push(1, 1).is_none());
+    assert!(!empty.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn keyed_windows_2() {
+    let _: KeyedWindows<u8, u8> = KeyedWindows::new(1).with_max_keys(0);
+}
+
+// counts pushes and reports a full window after every `period` elements
+struct CountingStorage {
+    inner: Storage<u32>,
+    pushes: Cell<usize>,
+    period: usize,
+}
+
+impl AsWindowStorage<u32> for CountingStorage {
+    fn window_size(&self) -> usize {
+        self.inner.window_size()
+    }
+
+    fn clear(&self) {
+        AsWindowStorage::clear(&self.inner);
+        self.pushes.set(0);
+    }
+
+    fn push(&self, elt: u32) -> bool {
+        self.pushes.set(self.pushes.get() + 1);
+        AsWindowStorage::push(&self.inner, elt) || self.pushes.get().is_multiple_of(self.period)
+    }
+
+    fn new_window(&self) -> Window<'_, u32> {
+        AsWindowStorage::new_window(&self.inner)
+    }
+}
+
+#[test]
+fn as_window_storage_1() {
+    let mut storage = CountingStorage { inner: Storage::new(3), pushes: Cell::new(0), period: 2 };
+    let output: Vec<Vec<u32>> = Adaptor::new(0..5, &mut storage).map(|w| w.iter().cloned().collect()).collect();
+
+    assert_eq!(output, vec![vec![0, 1], vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+    assert_eq!(storage.pushes.get(), 5);
+}
+
+#[test]
+fn windows_from_1() {
+    fn spread(window: &Window<'_, i32>) -> i32 {
+        window.iter().max().unwrap() - window.iter().min().unwrap()
+    }
+
+    let mut storage = Storage::new(0);
+    let windows: &[&[i32]] = &[&[1, 5, 3], &[4], &[-2, 2]];
+    let spreads: Vec<i32> = testing::windows_from(windows, &mut storage).map(|w| spread(&w)).collect();
+
+    assert_eq!(spreads, [4, 0, 4]);
+    assert_eq!(storage.window_size(), 3);
+}
+
+#[test]
+#[should_panic(expected = "next() called before previous Window went out of scope")]
+fn windows_from_2() {
+    let mut storage = Storage::new(0);
+    let windows: &[&[u8]] = &[&[1], &[2]];
+    let mut iter = testing::windows_from(windows, &mut storage);
+    let _first = iter.next();
+    let _second = iter.next();
+}
+
+#[test]
+fn assert_same_as_slice_windows_1() {
+    let data: Vec<u32> = (0..20).map(|x| x * 7 % 11).collect();
+
+    for window_size in 0..25 {
+        let count = testing::assert_same_as_slice_windows(&data, window_size, |w| w.cloned().collect::<Vec<_>>());
+        let expected = match window_size {
+            0 => 0,
+            x if x > data.len() => 1,
+            x => data.len() - x + 1,
+        };
+        assert_eq!(count, expected);
+    }
+
+    let strings = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(testing::assert_same_as_slice_windows(&strings, 2, |w| w.map(|s| s.len()).sum::<usize>()), 3);
+}
+
+#[test]
+#[should_panic(expected = "window 0 differs from the reference")]
+fn assert_same_as_slice_windows_2() {
+    let mut calls = 0;
+    // a computation with hidden state, which sees different inputs in both runs
+    testing::assert_same_as_slice_windows(&[1, 2, 3, 4], 3, |w| { calls += 1; w.sum::<i32>() + calls });
+}
+
+#[test]
+fn window_lifetimes_1() {
+    // helpers can return views borrowing only the window, not the storage
+    fn tail<'w>(window: &'w Window<'_, u32>) -> std::iter::Skip<WindowIter<'w, u32>> {
+        window.into_iter().skip(1)
+    }
+
+    fn bump(window: &mut Window<'_, u32>) {
+        for x in window {
+            *x += 10;
+        }
+    }
+
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..4).sliding_windows(&mut storage);
+
+    let mut window = windows.next().unwrap();
+    bump(&mut window);
+    assert_eq!(tail(&window).cloned().collect::<Vec<_>>(), [11, 12]);
+    drop(window);
+
+    let window = windows.next().unwrap();
+    assert_eq!(window, &[11, 12, 3][..]);
+}
+
+#[test]
+fn from_shared_1() {
+    let storage: std::rc::Rc<Storage<u32>> = std::rc::Rc::new(Storage::new(2));
+
+    {
+        let mut first = Adaptor::from_shared(0..3, &storage).unwrap();
+        assert!(storage.is_attached());
+        let err = Adaptor::from_shared(10..13, &storage).err().unwrap();
+        assert!(err.to_string().starts_with("Storage is already used by another Adaptor (window size 2"));
+
+        // the failed attempt didn't touch the buffered elements
+        assert_eq!(first.next().unwrap(), &[0, 1][..]);
+        assert_eq!(first.next().unwrap(), &[1, 2][..]);
+    }
+
+    assert!(!storage.is_attached());
+    let output: Vec<Vec<u32>> = Adaptor::from_shared(10..13, &storage).unwrap()
+        .map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, [[10, 11], [11, 12]]);
+}
+
+#[test]
+fn map_in_place_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut visited = Vec::new();
+
+    for mut window in (0..6).sliding_windows(&mut storage) {
+        visited.clear();
+        window.map_in_place(|x| { visited.push(*x); *x += 100; });
+    }
+
+    // the last window is rotated in the ring buffer, but visited in logical order
+    assert_eq!(visited, [203, 104, 5]);
+    assert_eq!(storage.into_vec(), [303, 204, 105]);
+}
+
+#[test]
+fn with_decay_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let output: Vec<Vec<u32>> = (1..6).map(|x| x * 1000)
+        .sliding_windows(&mut storage)
+        .with_decay(|x| *x /= 10)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+
+    assert_eq!(output, [[1000, 2000, 3000], [200, 300, 4000], [30, 400, 5000]]);
+
+    // a partial window is never decayed
+    let mut decays = 0;
+    assert_eq!((0..2).sliding_windows(&mut storage).with_decay(|_| decays += 1).count(), 1);
+    assert_eq!(decays, 0);
+}
+
+#[test]
+fn zip_kernel_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mask = [true, false, true];
+    let mut masked = Vec::new();
+
+    for window in (0..5).sliding_windows(&mut storage) {
+        let mut kept = Vec::new();
+        window.zip_kernel(&mask, |&x, &keep| if keep { kept.push(x) });
+        masked.push(kept);
+    }
+
+    assert_eq!(masked, [[0, 2], [1, 3], [2, 4]]);
+}
+
+#[test]
+#[should_panic(expected = "kernel length differs from window length")]
+fn zip_kernel_2() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let window = (0..5).sliding_windows(&mut storage).next().unwrap();
+    window.zip_kernel(&[1, 2], |_, _| ());
+}
+
+#[test]
+fn as_slice_pair_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+
+    for window in (0..6).sliding_windows(&mut storage) {
+        let expected: Vec<u32> = window.iter().cloned().collect();
+        let mut iter = window.iter();
+
+        for consumed in 0..4 {
+            let (first, second) = iter.as_slice_pair();
+            assert_eq!([first, second].concat(), &expected[consumed.min(3)..]);
+            iter.next();
+        }
+    }
+}
+
+#[test]
+fn by_ref_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut iter = 0..10;
+
+    {
+        let mut windows = Adaptor::new(&mut iter, &mut storage);
+        assert_eq!(windows.next().unwrap(), &[0, 1, 2][..]);
+        assert_eq!(windows.next().unwrap(), &[1, 2, 3][..]);
+    }
+    assert_eq!(iter.next(), Some(4));
+
+    // windowing the rest starts over with an empty storage
+    let output: Vec<Vec<u32>> = iter.by_ref().sliding_windows(&mut storage).take(1).map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, [[5, 6, 7]]);
+    assert_eq!(iter.collect::<Vec<_>>(), [8, 9]);
+}
+
+#[test]
+fn stop_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut iter = 0..10;
+
+    let (buffered, _) = {
+        let mut windows = iter.by_ref().sliding_windows(&mut storage);
+        for _ in 0..4 {
+            windows.next();
+        }
+        windows.stop()
+    };
+
+    assert_eq!(buffered, [3, 4, 5]);
+    assert_eq!(iter.collect::<Vec<_>>(), [6, 7, 8, 9]);
+    assert!(storage.capacity() >= 3);
+
+    // nothing is pulled before the first call to next()
+    let (buffered, rest) = (0..2).sliding_windows(&mut storage).stop();
+    assert!(buffered.is_empty());
+    assert_eq!(rest, 0..2);
+}
+
+#[test]
+fn on_short_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+
+    for &policy in &[OnShort::YieldPartial, OnShort::Empty, OnShort::Error] {
+        // long enough inputs are unaffected
+        assert_eq!((0..4).sliding_windows(&mut storage).on_short(policy).count(), 2);
+        assert_eq!((0..3).sliding_windows(&mut storage).on_short(policy).count(), 1);
+    }
+    assert_eq!((0..0).sliding_windows(&mut storage).on_short(OnShort::YieldPartial).count(), 0);
+    assert_eq!((0..0).sliding_windows(&mut storage).on_short(OnShort::Empty).count(), 0);
+
+    let mut windows = (0..2).sliding_windows(&mut storage).on_short(OnShort::Empty);
+    assert!(windows.next().is_none());
+    assert!(windows.next().is_none());
+}
+
+#[test]
+#[should_panic(expected = "input ended after 2 elements, before the first window of size 3 was full")]
+fn on_short_2() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    (0..2).sliding_windows(&mut storage).on_short(OnShort::Error).count();
+}
+
+#[test]
+#[should_panic(expected = "input ended after 0 elements")]
+fn on_short_3() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    (0..0).sliding_windows(&mut storage).on_short(OnShort::Error).count();
+}
+
+#[test]
+fn min_fill_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+
+    let output: Vec<Vec<u32>> = (0..5).sliding_windows(&mut storage).min_fill(1)
+        .map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, vec![vec![0], vec![0, 1], vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+
+    let windows = (0..5).sliding_windows(&mut storage).min_fill(2);
+    assert_eq!(windows.size_hint(), (4, Some(4)));
+    assert_eq!(windows.count(), 4);
+
+    // input shorter than min_fill
+    let windows = (0..1).sliding_windows(&mut storage).min_fill(2);
+    assert_eq!(windows.size_hint(), (0, Some(0)));
+    assert_eq!(windows.count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "min_fill 4 must be in 1..=3")]
+fn min_fill_2() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let _ = (0..5).sliding_windows(&mut storage).min_fill(4);
+}
+
+#[test]
+fn size_hint_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..6).sliding_windows(&mut storage);
+
+    for remaining in (0..4).rev() {
+        windows.next();
+        assert_eq!(windows.size_hint(), (remaining, Some(remaining)));
+    }
+    assert!(windows.next().is_none());
+    drop(windows);
+
+    assert_eq!((0..2).sliding_windows(&mut storage).on_short(OnShort::Empty).size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn window_capacity_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let levels: Vec<(usize, usize, bool)> = (0..4).sliding_windows(&mut storage).min_fill(1)
+        .map(|w| (w.fill(), w.capacity(), w.is_full()))
+        .collect();
+
+    assert_eq!(levels, [(1, 3, false), (2, 3, false), (3, 3, true), (3, 3, true)]);
+}
+
+#[test]
+fn reset_on_gap_1() {
+    let timestamps = [0u64, 1, 2, 3, 10, 11, 30, 31, 32];
+    let mut storage: Storage<u64> = Storage::new(3);
+
+    let output: Vec<Vec<u64>> = timestamps.iter().cloned().sliding_windows(&mut storage)
+        .reset_on_gap(|&t| t, 5)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    // the segment [10, 11] is too short
+    assert_eq!(output, [[0, 1, 2], [1, 2, 3], [30, 31, 32]]);
+
+    let output: Vec<Vec<u64>> = timestamps.iter().cloned().sliding_windows(&mut storage)
+        .reset_on_gap(|&t| t, 5)
+        .min_fill(2)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(output, vec![vec![0, 1], vec![0, 1, 2], vec![1, 2, 3], vec![10, 11], vec![30, 31], vec![30, 31, 32]]);
+
+    // a short last segment is a short input of its own
+    let output: Vec<Vec<u64>> = [0u64, 1, 2, 9].iter().cloned().sliding_windows(&mut storage)
+        .reset_on_gap(|&t| t, 5)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(output, [vec![0, 1, 2], vec![9]]);
+}
+
+#[test]
+fn tumbling_by_1() {
+    let mut storage: Storage<u32> = Storage::new(1);
+    let buckets: Vec<(u32, Vec<u32>, usize)> = [1, 3, 5, 4, 6, 9].iter().cloned()
+        .tumbling_by(&mut storage, |&x| x % 2)
+        .map(|(key, w)| (key, w.iter().cloned().collect(), w.capacity()))
+        .collect();
+
+    assert_eq!(buckets, [(1, vec![1, 3, 5], 3), (0, vec![4, 6], 2), (1, vec![9], 1)]);
+    assert_eq!(storage.window_size(), 1);
+    assert!(storage.capacity() >= 3);
+
+    let mut iter = (0..0).tumbling_by(&mut storage, |&x| x);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+#[should_panic(expected = "next() called before previous Window went out of scope")]
+fn tumbling_by_2() {
+    let mut storage: Storage<u32> = Storage::new(1);
+    let mut iter = (0..4).tumbling_by(&mut storage, |&x| x / 2);
+    let _a = iter.next();
+    let _b = iter.next();
+}
+
+#[test]
+fn rollup_1() {
+    let mut storage: Storage<f64> = Storage::new(3);
+    let spreads: Vec<f64> = (0..20).map(f64::from)
+        .rollup(4, |chunk| chunk.iter().sum::<f64>() / 4.0, &mut storage)
+        .map(|w| w.iter().cloned().fold(f64::MIN, f64::max) - w.iter().cloned().fold(f64::MAX, f64::min))
+        .collect();
+
+    // means 1.5, 5.5, 9.5, 13.5, 17.5
+    assert_eq!(spreads, [8.0, 8.0, 8.0]);
+
+    // a trailing incomplete chunk is dropped before windowing
+    assert_eq!((0..7).rollup(2, |c| f64::from(c[0]), &mut storage).map(|w| w.fill()).collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn sliding_windows_chain_1() {
+    let mut storage: Storage<u32> = Storage::new(4);
+    let windows: Vec<Vec<u32>> = sliding_windows_chain(vec![0..3, 3..3, 3..4, 4..6], &mut storage)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(windows, [[0, 1, 2, 3], [1, 2, 3, 4], [2, 3, 4, 5]]);
+
+    let windows: Vec<Vec<u32>> = sliding_windows_chain(vec![vec![1], vec![2]], &mut storage)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(windows, [[1, 2]]);
+}
+
+#[test]
+fn try_for_each_window_1() {
+    use std::ops::ControlFlow;
+
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..6).sliding_windows(&mut storage);
+
+    let first_large_sum = windows.try_for_each_window(|w| {
+        let sum: u32 = w.iter().sum();
+        if sum > 8 { ControlFlow::Break(sum) } else { ControlFlow::Continue(()) }
+    });
+    assert_eq!(first_large_sum, (ControlFlow::Break(9), 3));
+
+    // resumes after the window which broke
+    let rest = windows.try_for_each_window(|w| -> ControlFlow<()> {
+        assert_eq!(*w, &[3, 4, 5][..]);
+        ControlFlow::Continue(())
+    });
+    assert_eq!(rest, (ControlFlow::Continue(()), 1));
+    assert!(windows.next().is_none());
+}
+
+#[test]
+fn all_windows_1() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let mut visited = 0;
+    let sorted = [1, 2, 5, 4, 6].iter().cloned().sliding_windows(&mut storage)
+        .all_windows(|w| { visited += 1; w.is_monotonic_increasing() });
+    assert!(!sorted);
+    // stopped at [5, 4]
+    assert_eq!(visited, 3);
+
+    assert!((0..5).sliding_windows(&mut storage).all_windows(|w| w.is_strictly_increasing()));
+    assert!((0..0).sliding_windows(&mut storage).all_windows(|_| false));
+
+    let mut windows = (0..5).sliding_windows(&mut storage);
+    assert!(windows.any_window(|w| w.iter().any(|&x| x == 2)));
+    // stopped at [1, 2]
+    assert_eq!(windows.next().unwrap(), &[2, 3][..]);
+    drop(windows);
+    assert!(!(0..0).sliding_windows(&mut storage).any_window(|_| true));
+}
+
+#[test]
+fn run_windows_by_key_1() {
+    let sessions = [(1, 10), (1, 11), (1, 12), (2, 20), (3, 30), (3, 31), (1, 40), (1, 41)];
+    let mut storage = Storage::new(2);
+
+    let output: Vec<Vec<u32>> = sessions.iter().cloned()
+        .run_windows_by_key(&mut storage, |&(session, _)| session)
+        .map(|w| w.iter().map(|&(_, x)| x).collect())
+        .collect();
+
+    // session 2 is too short, session 1 occurs twice but isn't contiguous
+    assert_eq!(output, [[10, 11], [11, 12], [30, 31], [40, 41]]);
+}
+
+#[test]
+fn summarized_window_1() {
+    let mut storage: Storage<f64> = Storage::new(3);
+    let data = [2.0, -1.0, 4.0, 0.5];
+    let summaries: Vec<(f64, f64, f64)> = data.iter().cloned().sliding_windows(&mut storage)
+        .map(SummarizedWindow::new)
+        .map(|w| (w.sum(), w.min().unwrap(), w.max().unwrap()))
+        .collect();
+    assert_eq!(summaries, [(5.0, -1.0, 4.0), (3.5, -1.0, 4.0)]);
+
+    // derefs to the window
+    let window = SummarizedWindow::new((0..3).map(f64::from).sliding_windows(&mut storage).next().unwrap());
+    assert_eq!(window.fill(), 3);
+    assert_eq!(window.into_inner(), &[0.0, 1.0, 2.0][..]);
+
+    let mut storage = Storage::new(0);
+    let empty: &[&[u8]] = &[&[]];
+    let window = SummarizedWindow::new(testing::windows_from(empty, &mut storage).next().unwrap());
+    assert_eq!((window.sum(), window.min(), window.max()), (0, None, None));
+}
+
+#[test]
+fn window_iter_len_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut window = (0..5).sliding_windows(&mut storage).nth(1).unwrap();
+
+    let mut iter = window.iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.count(), 2);
+
+    let mut iter_mut = window.iter_mut();
+    iter_mut.nth(1);
+    assert_eq!(iter_mut.len(), 1);
+}
+
+#[test]
+fn monotonic_1() {
+    fn checks(w: &Window<'_, f64>) -> [bool; 5] {
+        [w.is_sorted(), w.is_monotonic_increasing(), w.is_strictly_increasing(),
+         w.is_monotonic_decreasing(), w.is_strictly_decreasing()]
+    }
+
+    let mut storage = Storage::new(0);
+    let windows: &[&[f64]] = &[&[1.0, 2.0, 3.0], &[1.0, 1.0, 2.0], &[3.0, 2.0, 1.0], &[2.0, 2.0], &[1.0, f64::NAN], &[7.0], &[]];
+    let results: Vec<[bool; 5]> = testing::windows_from(windows, &mut storage).map(|w| checks(&w)).collect();
+    assert_eq!(results, [
+        [true, true, true, false, false],
+        [true, true, false, false, false],
+        [false, false, false, true, true],
+        [true, true, false, true, false],
+        [false, false, false, false, false],
+        [true, true, true, true, true],
+        [true, true, true, true, true],
+    ]);
+
+    // rotated windows, the seam of the ring buffer is inside the window
+    let mut storage: Storage<u32> = Storage::new(4);
+    let increasing: Vec<bool> = [0, 1, 2, 3, 4, 5, 3].iter().cloned().sliding_windows(&mut storage)
+        .map(|w| w.is_strictly_increasing())
+        .collect();
+    assert_eq!(increasing, [true, true, true, false]);
+}
+
+#[test]
+fn predicates_1() {
+    let mut storage: Storage<u32> = Storage::new(4);
+    let results: Vec<(bool, bool, usize)> = (0..7).sliding_windows(&mut storage)
+        .map(|w| (w.all(|&x| x > 1), w.any(|&x| x == 5), w.count_where(|&x| x % 2 == 0)))
+        .collect();
+
+    assert_eq!(results, [(false, false, 2), (false, false, 2), (true, true, 2), (true, true, 2)]);
+
+    let mut storage = Storage::new(0);
+    let empty: &[&[u32]] = &[&[]];
+    let w = testing::windows_from(empty, &mut storage).next().unwrap();
+    assert_eq!((w.all(|_| false), w.any(|_| true), w.count_where(|_| true)), (true, false, 0));
+}
+
+#[test]
+fn rolling_skipnan_1() {
+    let nan = f64::NAN;
+    let data = [1.0, nan, 3.0, nan, nan, nan, 4.0];
+
+    let means: Vec<Option<f64>> = data.iter().cloned().rolling_mean_skipnan(3).collect();
+    assert_eq!(means, [Some(2.0), Some(3.0), Some(3.0), None, Some(4.0)]);
+
+    let sums: Vec<Option<f64>> = data.iter().cloned().rolling_sum_skipnan(3).min_periods(2).collect();
+    assert_eq!(sums, [Some(4.0), None, None, None, None]);
+
+    // with min_periods 0 an all-NaN window sums to 0
+    let sums: Vec<Option<f64>> = data.iter().cloned().rolling_sum_skipnan(3).min_periods(0).collect();
+    assert_eq!(sums[3], Some(0.0));
+
+    // integers are never NaN
+    let sums: Vec<Option<f64>> = (0..4).rolling_sum_skipnan(2).collect();
+    assert_eq!(sums, [Some(1.0), Some(3.0), Some(5.0)]);
+
+    assert_eq!((0..4).rolling_mean_skipnan(0).count(), 0);
+
+    assert_eq!((0..4).rolling_sum_skipnan(2).size_hint(), (3, Some(3)));
+    assert_eq!((0..4).rolling_mean_skipnan(0).size_hint(), (0, Some(0)));
+}
+
+#[test]
+#[should_panic(expected = "min_periods 4 must not exceed the window size 3")]
+fn rolling_skipnan_2() {
+    let _ = (0..4).rolling_mean_skipnan(3).min_periods(4);
+}
+
+#[test]
+fn sliding_windows_nulls_1() {
+    let data = [None, Some(1.0), None, None, Some(4.0), None];
+    let mut storage: Storage<f64> = Storage::new(3);
+
+    let collect = |storage: &mut Storage<f64>, policy| -> Vec<Vec<f64>> {
+        data.iter().cloned().sliding_windows_nulls(storage, policy)
+            .map(|w| w.iter().cloned().collect())
+            .collect()
+    };
+
+    assert_eq!(collect(&mut storage, NullPolicy::Skip), [[1.0, 4.0]]);
+    // the leading None has no value to carry forward
+    assert_eq!(collect(&mut storage, NullPolicy::ForwardFill),
+               [[1.0, 1.0, 1.0], [1.0, 1.0, 4.0], [1.0, 4.0, 4.0]]);
+    assert_eq!(collect(&mut storage, NullPolicy::Constant(0.0)),
+               [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 4.0], [0.0, 4.0, 0.0]]);
+
+    let fill = FillNulls::new(data.iter().cloned(), NullPolicy::Constant(0.0));
+    assert_eq!(fill.size_hint(), (6, Some(6)));
+}
+
+#[test]
+fn fill_1() {
+    let data = vec![None, None, Some(1), None, None, None, Some(5), Some(6), None];
+
+    let forward: Vec<Option<u32>> = data.iter().cloned().ffill().collect();
+    assert_eq!(forward, [None, None, Some(1), Some(1), Some(1), Some(1), Some(5), Some(6), Some(6)]);
+
+    let mut backward = data.iter().cloned().bfill();
+    assert_eq!(backward.size_hint(), (9, Some(9)));
+    backward.next();
+    assert_eq!(backward.size_hint(), (8, Some(8)));
+    assert_eq!(backward.collect::<Vec<_>>(), [Some(1), Some(1), Some(5), Some(5), Some(5), Some(5), Some(6), None]);
+
+    assert_eq!(Vec::<Option<u32>>::new().into_iter().bfill().count(), 0);
+    assert_eq!(vec![None::<u32>; 3].into_iter().bfill().collect::<Vec<_>>(), [None, None, None]);
+}
+
+#[test]
+fn time_buckets_1() {
+    let events = [(0u64, 1.0), (4, 3.0), (12, 5.0), (31, 7.0), (39, 9.0)];
+    let mean = |bucket: &[(u64, f64)]| bucket.iter().map(|&(_, x)| x).sum::<f64>() / bucket.len() as f64;
+
+    let skip: Vec<(u64, f64)> = events.iter().cloned().time_buckets(10, |e| e.0, mean, EmptyBucket::Skip).collect();
+    assert_eq!(skip, [(0, 2.0), (10, 5.0), (30, 8.0)]);
+
+    let repeat: Vec<(u64, f64)> = events.iter().cloned().time_buckets(10, |e| e.0, mean, EmptyBucket::Repeat).collect();
+    assert_eq!(repeat, [(0, 2.0), (10, 5.0), (20, 5.0), (30, 8.0)]);
+
+    let fill: Vec<(u64, f64)> = events.iter().cloned().time_buckets(5, |e| e.0, mean, EmptyBucket::Fill(0.0)).collect();
+    assert_eq!(fill, [(0, 2.0), (5, 0.0), (10, 5.0), (15, 0.0), (20, 0.0), (25, 0.0), (30, 7.0), (35, 9.0)]);
+}
+
+#[test]
+#[should_panic(expected = "timestamps must be non-decreasing")]
+fn time_buckets_2() {
+    [30u64, 10].iter().cloned().time_buckets(10, |&t| t, |b| b.len(), EmptyBucket::Skip).count();
+}
+
+#[test]
+fn window_bus_1() {
+    let mut pairs = Vec::new();
+    let mut triples = Vec::new();
+    let mut too_long = Vec::new();
+    let mut never = 0;
+
+    let consumed = WindowBus::new()
+        .subscribe(2, |w| pairs.push(w.iter().cloned().collect::<Vec<String>>().concat()))
+        .subscribe(3, |w| triples.push(w.iter().cloned().collect::<Vec<String>>().concat()))
+        .subscribe(5, |w| too_long.push(w.fill()))
+        .subscribe(0, |_| never += 1)
+        .run("abcd".chars().map(|c| c.to_string()));
+
+    assert_eq!(consumed, 4);
+    assert_eq!(pairs, ["ab", "bc", "cd"]);
+    assert_eq!(triples, ["abc", "bcd"]);
+    assert_eq!(too_long, [4]);
+    assert_eq!(never, 0);
+
+    assert_eq!(WindowBus::<u8>::default().run(0..3), 3);
+}
+
+#[test]
+fn fixed_storage_1() {
+    let mut storage: FixedStorage<u32, 2> = FixedStorage::new();
+    let output: Vec<Vec<u32>> = (0..4).sliding_windows(&mut storage).map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, [[0, 1], [1, 2], [2, 3]]);
+
+    // reusable like a Storage
+    assert_eq!((0..1).sliding_windows(&mut storage).map(|w| w.fill()).collect::<Vec<_>>(), [1]);
+    assert_eq!(storage.into_vec(), [0]);
+
+    let mut empty: FixedStorage<u32, 0> = FixedStorage::default();
+    assert_eq!((0..4).sliding_windows(&mut empty).count(), 0);
+}
+
+#[test]
+fn array_storage_1() {
+    use std::rc::Rc;
+
+    let mut storage: ArrayStorage<u32, 3> = ArrayStorage::new();
+    let mut output = Vec::new();
+    for mut window in (0..6).sliding_windows(&mut storage) {
+        output.push(*window.as_array::<3>());
+    }
+    assert_eq!(output, [[0, 1, 2], [1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+
+    // partial trailing windows and reuse
+    let suffix: Vec<Vec<u32>> = (0..4).sliding_windows(&mut storage).with_partial_suffix(true)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(suffix, [vec![0, 1, 2], vec![1, 2, 3], vec![2, 3], vec![3]]);
+    assert_eq!(storage.len(), 3);
+
+    let mut empty: ArrayStorage<u32, 0> = ArrayStorage::default();
+    assert_eq!((0..4).sliding_windows(&mut empty).count(), 0);
+
+    // every element is dropped exactly once, evicted, cleared or with the storage
+    let counter = Rc::new(());
+    let mut storage: ArrayStorage<Rc<()>, 2> = ArrayStorage::new();
+    assert_eq!((0..5).map(|_| counter.clone()).sliding_windows(&mut storage).count(), 4);
+    assert_eq!(Rc::strong_count(&counter), 3);
+    assert_eq!((0..1).map(|_| counter.clone()).sliding_windows(&mut storage).count(), 1);
+    assert_eq!(Rc::strong_count(&counter), 2);
+    drop(storage);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+#[should_panic(expected = "next() called before previous Window went out of scope (array storage of 2 elements)")]
+fn array_storage_2() {
+    let mut storage: ArrayStorage<u32, 2> = ArrayStorage::new();
+    let mut windows = (0..4).sliding_windows(&mut storage);
+    let _a = windows.next();
+    let _b = windows.next();
+}
+
+#[test]
+fn array_windows_1() {
+    let windows = (0..5).array_windows::<3>();
+    assert_eq!(windows.size_hint(), (3, Some(3)));
+    assert_eq!(windows.collect::<Vec<_>>(), [[0, 1, 2], [1, 2, 3], [2, 3, 4]]);
+
+    // the windows of a slice iterator hold references
+    let words = ["a", "b", "c"].map(String::from);
+    let pairs: Vec<[&String; 2]> = words.iter().array_windows().collect();
+    assert_eq!(pairs, [["a", "b"], ["b", "c"]]);
+
+    assert_eq!((0..2).array_windows::<3>().count(), 0);
+}
+
+#[test]
+fn as_array_1() {
+    let mut storage: FixedStorage<u32, 3> = FixedStorage::new();
+    let mut output = Vec::new();
+    for mut window in (0..7).sliding_windows(&mut storage) {
+        let arr = window.as_mut_array::<3>();
+        output.push(*arr);
+        // the rotation and the modification persist into the following windows
+        arr[2] *= 10;
+    }
+    assert_eq!(output, [[0, 1, 2], [1, 20, 3], [20, 30, 4], [30, 40, 5], [40, 50, 6]]);
+    assert_eq!(storage.into_vec(), [40, 50, 60]);
+}
+
+#[test]
+#[should_panic(expected = "window holds 2 elements, not 3")]
+fn as_array_2() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..2).sliding_windows(&mut storage).on_short(OnShort::YieldPartial);
+    let _: &[u32; 3] = windows.next().unwrap().as_array();
+}
+
+#[test]
+fn cloned_peekable_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..6).sliding_windows(&mut storage).cloned_peekable();
+    assert_eq!(windows.size_hint(), (4, Some(4)));
+
+    // peeked windows stay valid while the adaptor advances
+    let first = windows.peek().cloned();
+    let mut output = Vec::new();
+    while let Some(window) = windows.next_if(|w| w[0] % 2 == 0 || w[2] < 4) {
+        output.push(window);
+    }
+    assert_eq!(first, Some(vec![0, 1, 2]));
+    assert_eq!(output, [[0, 1, 2], [1, 2, 3], [2, 3, 4]]);
+    assert_eq!(windows.next(), Some(vec![3, 4, 5]));
+    assert_eq!(windows.next(), None);
+}
+
+#[cfg(feature = "itertools")]
+#[test]
+fn into_owned_1() {
+    let mut storage: Storage<u32> = Storage::new(10);
+    let windows: Vec<_> = (0..12).sliding_windows(&mut storage).into_owned().collect();
+    assert_eq!(windows.len(), 3);
+    // windows larger than the inline capacity spill onto the heap
+    assert!(windows.iter().all(|w| w.spilled()));
+    assert_eq!(&windows[2][..], &(2..12).collect::<Vec<u32>>()[..]);
+
+    let mut storage: Storage<u32> = Storage::new(2);
+    let mut windows = (0..3).sliding_windows(&mut storage).into_owned();
+    let first = windows.next().unwrap();
+    let second = windows.next().unwrap();
+    assert!(!first.spilled());
+    assert_eq!((&first[..], &second[..]), (&[0, 1][..], &[1, 2][..]));
+}
+
+#[test]
+fn as_cow_1() {
+    use std::borrow::Cow;
+
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut borrowed = Vec::new();
+    for mut window in (0..8).sliding_windows(&mut storage) {
+        let cow = window.as_cow();
+        assert_eq!(&cow[..], &window.iter().cloned().collect::<Vec<_>>()[..]);
+        borrowed.push(matches!(cow, Cow::Borrowed(_)));
+        if cow[0] == 2 {
+            window.make_contiguous();
+            assert!(matches!(window.as_cow(), Cow::Borrowed(&[2, 3, 4])));
+        }
+    }
+    // the window is contiguous every window size elements, make_contiguous shifts the phase
+    assert_eq!(borrowed, [true, false, false, false, false, true]);
+}
+
+#[test]
+fn bits_1() {
+    let bits: Vec<bool> = [0b1010_0000u8, 0xff].iter().cloned().bits().collect();
+    assert_eq!(&bits[..8], &[true, false, true, false, false, false, false, false]);
+    assert!(bits[8..].iter().all(|&bit| bit));
+
+    let mut iter = [0u8, 1].iter().cloned().bits();
+    iter.next();
+    assert_eq!(iter.size_hint(), (15, Some(15)));
+}
+
+#[test]
+fn bit_windows_1() {
+    let bytes = [0b1100_1010u8, 0b0101_1111, 0x0f];
+    let windows: Vec<u16> = bytes.iter().cloned().bit_windows(12).collect();
+    assert_eq!(windows.len(), 24 - 12 + 1);
+    assert_eq!(windows[0], 0b1100_1010_0101);
+    assert_eq!(windows[4], 0b1010_0101_1111);
+    assert_eq!(windows[12], 0b1111_0000_1111);
+
+    // every window equals the corresponding bits read as a number
+    let bits: Vec<bool> = bytes.iter().cloned().bits().collect();
+    for (window, expected) in windows.iter().zip(bits.windows(12)) {
+        assert_eq!(*window, expected.iter().fold(0, |acc, &bit| acc << 1 | bit as u16));
+    }
+
+    let mut iter = bytes.iter().cloned().bit_windows::<u16>(12);
+    assert_eq!(iter.size_hint(), (13, Some(13)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (12, Some(12)));
+
+    // the register is wider than 64 bits
+    let wide: Vec<u128> = [0xffu8; 17].iter().cloned().bit_windows(128).collect();
+    assert_eq!(wide, [u128::MAX; 9]);
+    assert_eq!([0xffu8].iter().cloned().bit_windows::<u64>(9).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "window size 9 must be in 1..=8")]
+fn bit_windows_2() {
+    [0u8].iter().cloned().bit_windows::<u8>(9);
+}
+
+#[test]
+fn find_sync_1() {
+    // two copies of 0x1a at bit offsets 0 and 12, one of them with a flipped bit
+    let stream = [0x1au8, 0x01, 0xa0, 0x00];
+    assert_eq!(stream.iter().cloned().find_sync(&[0x1a], 0).collect::<Vec<_>>(), [0, 12]);
+
+    let corrupted = [0x1au8, 0x03, 0xa0, 0x00];
+    assert_eq!(corrupted.iter().cloned().find_sync(&[0x1a], 0).collect::<Vec<_>>(), [0]);
+    assert_eq!(corrupted.iter().cloned().find_sync(&[0x1a], 1).collect::<Vec<_>>(), [0, 12]);
+
+    // every offset matches within the full budget
+    assert_eq!(stream.iter().cloned().find_sync(&[0x1a], 8).count(), 32 - 8 + 1);
+
+    // a pattern of the full register width
+    let pattern = [0x5au8; 16];
+    let mut long = vec![0u8];
+    long.extend_from_slice(&pattern);
+    assert_eq!(long.iter().cloned().find_sync(&pattern, 0).collect::<Vec<_>>(), [8]);
+}
+
+#[test]
+#[should_panic(expected = "pattern length 0 must be in 1..=16 bytes")]
+fn find_sync_2() {
+    [0u8].iter().cloned().find_sync(&[], 0);
+}
+
+#[test]
+fn char_windows_1() {
+    fn collect(text: &str, window_size: usize) -> Vec<String> {
+        let mut windows = text.chars().char_windows(window_size);
+        let mut all = Vec::new();
+        while let Some(window) = windows.next_window() {
+            all.push(window.to_owned());
+        }
+        assert_eq!(windows.next_window(), None);
+        all
+    }
+
+    // windows of multi-byte chars, long enough to move the buffer several times
+    let text = "añ€😀".repeat(10);
+    let chars: Vec<char> = text.chars().collect();
+    let expected: Vec<String> = chars.windows(5).map(|w| w.iter().collect()).collect();
+    assert_eq!(collect(&text, 5), expected);
+
+    assert_eq!(collect("ab", 1), ["a", "b"]);
+    assert_eq!(collect("ab", 3), ["ab"]);
+    assert!(collect("", 3).is_empty());
+    assert!(collect("ab", 0).is_empty());
+}
+
+#[test]
+fn find_subsequence_1() {
+    fn naive(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+        haystack.windows(needle.len()).enumerate().filter(|&(_, w)| w == needle).map(|(i, _)| i).collect()
+    }
+
+    let haystack: Vec<u8> = (0..2000u32).map(|i| (i * i % 7) as u8).collect();
+    for needle in &[&[0u8][..], &[1, 4], &[2, 4, 2], &[0, 1, 4, 2, 2, 4, 1], &[3, 3, 3]] {
+        let found: Vec<usize> = haystack.iter().cloned().find_subsequence(needle).collect();
+        assert_eq!(found, naive(&haystack, needle));
+    }
+
+    // overlapping matches
+    assert_eq!(b"aaaa".iter().cloned().find_subsequence(b"aa").collect::<Vec<_>>(), [0, 1, 2]);
+    // a needle longer than the haystack
+    assert_eq!(b"ab".iter().cloned().find_subsequence(b"abc").count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "needle must not be empty")]
+fn find_subsequence_2() {
+    b"ab".iter().cloned().find_subsequence(b"");
+}
+
+#[test]
+fn byte_windows_1() {
+    let bytes: Vec<u8> = (0..10).collect();
+    let windows = byte_windows::<3>(&bytes);
+    assert_eq!(windows.len(), 8);
+    assert!(windows.zip(bytes.windows(3)).all(|(a, b)| &a[..] == b));
+
+    // the windows point into the input
+    let first = byte_windows::<3>(&bytes).next().unwrap();
+    assert_eq!(first.as_ptr(), bytes.as_ptr());
+
+    assert_eq!(byte_windows::<4>(&bytes).next_back(), Some(&[6, 7, 8, 9]));
+    assert_eq!(byte_windows::<4>(&bytes).nth(2), Some(&[2, 3, 4, 5]));
+    assert_eq!(byte_windows::<11>(&bytes).next(), None);
+}
+
+#[test]
+fn edit_distance_1() {
+    fn levenshtein(a: &[u8], b: &[u8]) -> usize {
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        for (i, x) in a.iter().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, y) in b.iter().enumerate() {
+                let value = (previous[j] + (x != y) as usize).min(previous[j + 1] + 1).min(current[j] + 1);
+                current.push(value);
+            }
+            previous = current;
+        }
+        previous[b.len()]
+    }
+
+    let data = b"abracadabra alakazam abba cadabra";
+    let reference = b"cadab";
+    for band in 0..6 {
+        let distances: Vec<Option<usize>> = data.iter().cloned().edit_distance(reference, band).collect();
+        let expected: Vec<Option<usize>> = data.windows(reference.len())
+            .map(|w| Some(levenshtein(w, reference)).filter(|&d| d <= band))
+            .collect();
+        assert_eq!(distances, expected, "band {}", band);
+    }
+
+    let mut iter = data.iter().cloned().edit_distance(reference, 1);
+    assert_eq!(iter.size_hint(), (data.len() - 4, Some(data.len() - 4)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (data.len() - 5, Some(data.len() - 5)));
+    assert_eq!(data.iter().cloned().edit_distance(&[], 1).count(), 0);
+}
+
+#[test]
+fn hamming_distance_1() {
+    let data = [1, 2, 3, 1, 2, 4];
+    let distances: Vec<usize> = data.iter().cloned().hamming_distance(&[1, 2, 3]).collect();
+    assert_eq!(distances, [0, 3, 3, 1]);
+    assert_eq!(data.iter().cloned().hamming_distance(&[]).count(), 0);
+    assert_eq!(data[..2].iter().cloned().hamming_distance(&[1, 2, 3]).count(), 0);
+}
+
+#[test]
+fn rolling_entropy_1() {
+    fn entropy(window: &[u8]) -> f64 {
+        let mut counts = [0usize; 256];
+        for &byte in window {
+            counts[byte as usize] += 1;
+        }
+        let n = window.len() as f64;
+        counts.iter().filter(|&&c| c > 0).map(|&c| -(c as f64 / n) * (c as f64 / n).log2()).sum()
+    }
+
+    // a pseudo random stream with a constant region in the middle
+    let mut state = 1u32;
+    let data: Vec<u8> = (0..4000).map(|i| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        if (1500..2500).contains(&i) { 7 } else { (state >> 16) as u8 }
+    }).collect();
+
+    for &window_size in &[1, 3, 256, 1000] {
+        let output: Vec<f64> = data.iter().cloned().rolling_entropy(window_size).collect();
+        assert_eq!(output.len(), data.len() - window_size + 1);
+        for (x, window) in output.iter().zip(data.windows(window_size)) {
+            assert!((x - entropy(window)).abs() < 1e-9, "{} != {}", x, entropy(window));
+        }
+    }
+
+    let output: Vec<f64> = data.iter().cloned().rolling_entropy(1000).collect();
+    assert!(output[1500] < 1e-9);
+    assert!(output[0] > 7.5);
+    assert_eq!(data.iter().cloned().rolling_entropy(0).count(), 0);
+}
+
+#[test]
+fn rolling_checksums_1() {
+    fn adler32(data: &[u8]) -> u32 {
+        let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &x| {
+            let a = (a + x as u32) % 65521;
+            (a, (b + a) % 65521)
+        });
+        b << 16 | a
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        !data.iter().fold(!0u32, |mut crc, &x| {
+            crc ^= x as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            }
+            crc
+        })
+    }
+
+    let mut state = 7u32;
+    let data: Vec<u8> = (0..3000).map(|_| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (state >> 16) as u8
+    }).collect();
+
+    for &window_size in &[1, 2, 16, 300] {
+        let adler: Vec<u32> = data.iter().cloned().rolling_adler32(window_size).collect();
+        let crc: Vec<u32> = data.iter().cloned().rolling_crc32(window_size).collect();
+        let expected_adler: Vec<u32> = data.windows(window_size).map(adler32).collect();
+        let expected_crc: Vec<u32> = data.windows(window_size).map(crc32).collect();
+        assert_eq!(adler, expected_adler, "window size {}", window_size);
+        assert_eq!(crc, expected_crc, "window size {}", window_size);
+    }
+
+    // windows larger than the Adler-32 modulus
+    let ones = vec![0xffu8; 70000];
+    let adler: Vec<u32> = ones.iter().cloned().rolling_adler32(66000).collect();
+    assert!(adler.iter().all(|&x| x == adler32(&ones[..66000])));
+
+    let mut iter = data.iter().cloned().rolling_crc32(16);
+    assert_eq!(iter.size_hint(), (2985, Some(2985)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (2984, Some(2984)));
+    assert_eq!(data.iter().cloned().rolling_adler32(0).count(), 0);
+}
+
+#[test]
+fn runs_1() {
+    let data = [3, 3, 1, 1, 1, 2, 3, 3];
+    let runs: Vec<(u32, usize, usize)> = data.iter().cloned().runs().map(|r| (r.value, r.start, r.len)).collect();
+    assert_eq!(runs, [(3, 0, 2), (1, 2, 3), (2, 5, 1), (3, 6, 2)]);
+    assert_eq!(runs.iter().map(|r| r.2).sum::<usize>(), data.len());
+
+    // predicate runs compare with the first element of the run
+    let drift: Vec<usize> = (0..10).runs_by(|a, b| b - a <= 2).map(|r| r.len).collect();
+    assert_eq!(drift, [3, 3, 3, 1]);
+
+    let mut runs = data.iter().runs();
+    assert_eq!(runs.size_hint(), (1, Some(8)));
+    runs.next();
+    assert_eq!(runs.size_hint(), (1, Some(6)));
+    assert_eq!(runs.by_ref().count(), 3);
+    assert_eq!(runs.size_hint(), (0, Some(0)));
+    assert_eq!((0..0).runs().count(), 0);
+}
+
+#[test]
+fn label_extrema_1() {
+    let data = [5, 1, 4, 2, 8, 3, 3, 9, 0, 7];
+    for radius in 1..12 {
+        let labels: Vec<Extremum> = data.iter().label_extrema(radius).collect();
+        let expected: Vec<Extremum> = (0..data.len()).map(|i| {
+            let neighbours: Vec<i32> = (i.saturating_sub(radius)..data.len().min(i + radius + 1))
+                .filter(|&j| j != i)
+                .map(|j| data[j])
+                .collect();
+            if neighbours.iter().all(|&x| data[i] < x) {
+                Extremum::Min
+            } else if neighbours.iter().all(|&x| data[i] > x) {
+                Extremum::Max
+            } else {
+                Extremum::Neither
+            }
+        }).collect();
+        assert_eq!(labels, expected, "radius {}", radius);
+    }
+
+    let mut labels = data.iter().label_extrema(2);
+    assert_eq!(labels.size_hint(), (10, Some(10)));
+    labels.nth(3);
+    assert_eq!(labels.size_hint(), (6, Some(6)));
+    assert_eq!([1].iter().label_extrema(3).collect::<Vec<_>>(), [Extremum::Neither]);
+    assert_eq!(data[..0].iter().label_extrema(3).count(), 0);
+}
+
+#[test]
+fn hysteresis_1() {
+    let signal = [0.0, 5.0, 9.9, 10.0, 5.0, 1.0, 0.5, 5.0, f64::NAN, 12.0, f64::NAN];
+    let state: Vec<bool> = signal.iter().cloned().hysteresis(1.0, 10.0).collect();
+    assert_eq!(state, [false, false, false, true, true, false, false, false, false, true, true]);
+
+    // equal thresholds degrade to a plain threshold
+    let state: Vec<bool> = (0..5).hysteresis(2.0, 2.0).collect();
+    assert_eq!(state, [false, false, true, true, true]);
+}
+
+#[test]
+#[should_panic(expected = "low threshold 3 must not exceed the high threshold 2")]
+fn hysteresis_2() {
+    (0..5).hysteresis(3.0, 2.0);
+}
+
+#[test]
+fn rolling_top_k_by_key_1() {
+    let mut state = 3u32;
+    let data: Vec<u32> = (0..500).map(|_| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (state >> 16) % 50
+    }).collect();
+
+    for &(window_size, k) in &[(1, 1), (10, 3), (10, 10), (10, 20), (64, 5)] {
+        // keyed by the value modulo 10, so ties are frequent
+        let top: Vec<Vec<u32>> = data.iter().cloned().rolling_top_k_by_key(window_size, k, |x| x % 10).collect();
+        let expected: Vec<Vec<u32>> = data.windows(window_size).map(|window| {
+            let mut sorted: Vec<(u32, usize, u32)> = window.iter().enumerate().map(|(i, &x)| (x % 10, i, x)).collect();
+            sorted.sort();
+            sorted.iter().rev().take(k).map(|&(_, _, x)| x).collect()
+        }).collect();
+        assert_eq!(top, expected, "window size {}, k {}", window_size, k);
+    }
+
+    assert_eq!(data.iter().rolling_top_k_by_key(0, 3, |&&x| x).count(), 0);
+    assert!(data.iter().rolling_top_k_by_key(5, 0, |&&x| x).all(|top| top.is_empty()));
+}
+
+#[test]
+fn recent_set_1() {
+    let data = [1, 2, 1, 3, 4, 1, 1, 5, 6, 7, 2];
+    let mut recent = RecentSet::new(3);
+    let mut seen = Vec::new();
+    for (i, &x) in data.iter().enumerate() {
+        seen.push(recent.push(x));
+        let window = &data[(i + 1).saturating_sub(3)..=i];
+        for value in 0..10 {
+            assert_eq!(recent.contains(&value), window.contains(&value));
+        }
+    }
+    assert_eq!(seen, [false, false, true, false, false, true, true, false, false, false, false]);
+    assert_eq!((recent.len(), recent.window_size()), (3, 3));
+
+    let mut empty = RecentSet::new(0);
+    assert!(!empty.push(1) && !empty.push(1));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn dedup_within_1() {
+    let data = [1, 1, 2, 1, 3, 4, 5, 1, 1];
+    let deduped: Vec<u32> = data.iter().cloned().dedup_within(2).collect();
+    assert_eq!(deduped, [1, 2, 3, 4, 5, 1]);
+
+    let all: Vec<u32> = data.iter().cloned().dedup_within(0).collect();
+    assert_eq!(all, data);
+    assert_eq!(data.iter().dedup_within(0).size_hint(), (9, Some(9)));
+    assert_eq!(data.iter().dedup_within(100).collect::<Vec<_>>(), [&1, &2, &3, &4, &5]);
+
+    // the remaining elements may all be duplicates
+    let mut iter = [1, 1].iter().cloned().dedup_within(2);
+    assert_eq!(iter.size_hint(), (1, Some(2)));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.size_hint(), (0, Some(1)));
+    assert_eq!(iter.next(), None);
+}
+
+#[cfg(feature = "bloom")]
+#[test]
+fn approx_recent_set_1() {
+    let window_size = 5000;
+    let mut recent = ApproxRecentSet::new(window_size, 0.01);
+    for round in 0..4u64 {
+        for x in 0..window_size as u64 {
+            recent.push(&(round * 1_000_000 + x));
+        }
+        // no false negatives within the window
+        assert!((0..window_size as u64).all(|x| recent.contains(&(round * 1_000_000 + x))));
+    }
+
+    // values never pushed are rarely reported
+    let false_positives = (0..10_000u64).filter(|x| recent.contains(&(u64::MAX - x))).count();
+    assert!(false_positives < 200, "{} false positives", false_positives);
+
+    let mut empty: ApproxRecentSet<str> = ApproxRecentSet::new(0, 0.5);
+    assert!(!empty.push("a") && !empty.push("a"));
+}
+
+#[cfg(feature = "bloom")]
+#[test]
+#[should_panic(expected = "false positive rate 1 must be in (0, 1)")]
+fn approx_recent_set_2() {
+    ApproxRecentSet::<u32>::new(10, 1.0);
+}
+
+#[test]
+fn recent_sample_1() {
+    // every position of the window is sampled with probability k / window_size
+    let (window_size, k, trials) = (10, 3, 20_000);
+    let mut hits = [0usize; 10];
+    let mut sample = RecentSample::new(window_size, k).with_seed(42);
+    for i in 0..trials * window_size {
+        sample.push(i);
+        if i % window_size == window_size - 1 {
+            let values = sample.sample();
+            assert_eq!(values.len(), k);
+            assert!(values.windows(2).all(|w| w[0] < w[1]));
+            for &&value in &values {
+                assert!(value + window_size > i, "{} is not in the window ending at {}", value, i);
+                hits[value % window_size] += 1;
+            }
+        }
+    }
+    for &count in &hits {
+        let expected = trials * k / window_size;
+        assert!((count as f64 - expected as f64).abs() < 0.05 * expected as f64, "{:?}", hits);
+    }
+
+    let mut short = RecentSample::new(10, 5);
+    short.push('a');
+    short.push('b');
+    assert_eq!(short.sample(), [&'a', &'b']);
+    let mut empty = RecentSample::new(10, 0);
+    empty.push(1);
+    assert!(empty.sample().is_empty());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn iter_shuffled_1() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut storage: Storage<usize> = Storage::new(4);
+    // counts how often each logical position is visited first
+    let mut first = [0usize; 4];
+    for window in (0..4000).sliding_windows(&mut storage) {
+        let shuffled: Vec<usize> = window.iter_shuffled(&mut rng).cloned().collect();
+        let mut sorted = shuffled.clone();
+        sorted.sort();
+        assert_eq!(window, &sorted[..]);
+        first[shuffled[0] - window.iter().next().unwrap()] += 1;
+    }
+    assert!(first.iter().all(|&count| count > 900 && count < 1100), "{:?}", first);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn seeded_streams_1() {
+    use testing::{seeded_normal, seeded_uniform};
+
+    // reproducible for a seed
+    let a: Vec<f64> = seeded_normal(7, 0.0, 1.0).take(100).collect();
+    let b: Vec<f64> = seeded_normal(7, 0.0, 1.0).take(100).collect();
+    let c: Vec<f64> = seeded_normal(8, 0.0, 1.0).take(100).collect();
+    assert_eq!(a, b);
+    assert!(a != c);
+
+    // a uniform distribution on [0, 12) has mean 6 and variance 12
+    let data: Vec<f64> = seeded_uniform(1, 0.0, 12.0).take(30_000).collect();
+    assert!(data.iter().all(|&x| (0.0..12.0).contains(&x)));
+    for mean in data.iter().cloned().rolling_mean(20_000).step_by(1000) {
+        assert!((mean - 6.0).abs() < 0.1, "mean {}", mean);
+    }
+    for variance in data.iter().cloned().rolling_cov(data.iter().cloned(), 20_000).step_by(1000) {
+        assert!((variance - 12.0).abs() < 0.5, "variance {}", variance);
+    }
+
+    // the rolling mean of a normal stream narrows with the window size
+    let data: Vec<f64> = seeded_normal(2, 5.0, 3.0).take(10_000).collect();
+    let spread = |window_size| {
+        let means: Vec<f64> = data.iter().cloned().rolling_mean(window_size).collect();
+        means.iter().map(|m| (m - 5.0).abs()).fold(0.0, f64::max)
+    };
+    assert!(spread(1000) < spread(10));
+    assert!(spread(1000) < 0.5);
+}
+
+#[test]
+fn window_stats_1() {
+    let data = [4.0, -2.5, 7.0, 1.0, 1.0, 9.5, -3.0];
+    let mut storage: Storage<f64> = Storage::new(4);
+    for (window, slice) in data.iter().cloned().sliding_windows(&mut storage).zip(data.windows(4)) {
+        let stats = window.stats();
+        let mean = slice.iter().sum::<f64>() / 4.0;
+        let variance = slice.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 3.0;
+        assert_eq!(stats.count, 4);
+        assert!((stats.mean - mean).abs() < 1e-12);
+        assert!((stats.variance - variance).abs() < 1e-12);
+        assert_eq!(stats.min, slice.iter().cloned().reduce(f64::min));
+        assert_eq!(stats.max, slice.iter().cloned().reduce(f64::max));
+    }
+
+    let mut storage = Storage::new(0);
+    let empty = testing::windows_from(&[&[] as &[u32], &[7]], &mut storage).map(|w| w.stats()).collect::<Vec<_>>();
+    assert!(empty[0].mean.is_nan() && empty[0].variance.is_nan());
+    assert_eq!((empty[0].count, empty[0].min), (0, None));
+    assert_eq!((empty[1].count, empty[1].mean, empty[1].max), (1, 7.0, Some(7)));
+    assert!(empty[1].variance.is_nan());
+}
+
+#[test]
+fn emit_every_1() {
+    let data: Vec<u32> = (0..20).collect();
+    for k in 1..6 {
+        let mut storage: Storage<u32> = Storage::new(4);
+        let mut windows = data.iter().cloned().sliding_windows(&mut storage).emit_every(k);
+        let mut hints = vec![windows.size_hint()];
+        let mut output: Vec<Vec<u32>> = Vec::new();
+        while let Some(window) = windows.next() {
+            output.push(window.iter().cloned().collect());
+            drop(window);
+            hints.push(windows.size_hint());
+        }
+
+        let expected: Vec<Vec<u32>> = data.windows(4).step_by(k).map(|w| w.to_vec()).collect();
+        assert_eq!(output, expected, "k {}", k);
+        // the size hint is exact
+        for (i, hint) in hints.iter().enumerate() {
+            assert_eq!(*hint, (expected.len() - i, Some(expected.len() - i)), "k {}", k);
+        }
+    }
+
+    // decay is applied on every slide, including dropped ones
+    let mut storage: Storage<f64> = Storage::new(3);
+    let output: Vec<Vec<f64>> = vec![8.0; 7].into_iter().sliding_windows(&mut storage)
+        .with_decay(|x| *x *= 0.5)
+        .emit_every(2)
+        .map(|w| w.iter().cloned().collect())
+        .collect();
+    assert_eq!(output, [[8.0, 8.0, 8.0], [2.0, 4.0, 8.0], [2.0, 4.0, 8.0]]);
+}
+
+#[test]
+#[should_panic(expected = "emit_every must be positive")]
+fn emit_every_2() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let _ = (0..4).sliding_windows(&mut storage).emit_every(0);
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn spawn_fanout_1() {
+    use std::thread;
+    use std::time::Duration;
+
+    let mut storage: Storage<u64> = Storage::new(4);
+    let mut results = (0..200u64).sliding_windows(&mut storage)
+        .spawn_fanout(3, |window| {
+            // make later windows finish first on some workers
+            thread::sleep(Duration::from_micros(200 - window[0]));
+            window
+        });
+    assert_eq!(results.size_hint(), (197, Some(197)));
+    let first = results.next().unwrap();
+    assert_eq!(first, [0, 1, 2, 3]);
+    let rest: Vec<Vec<u64>> = results.collect();
+    assert_eq!(rest.len(), 196);
+    assert!(rest.iter().enumerate().all(|(i, w)| w[..] == [i as u64 + 1, i as u64 + 2, i as u64 + 3, i as u64 + 4]));
+
+    // dropping the handle early joins the workers
+    let mut storage: Storage<u64> = Storage::new(2);
+    let mut results = (0..100u64).sliding_windows(&mut storage).spawn_fanout(2, |window| window[1]);
+    assert_eq!(results.next(), Some(1));
+    drop(results);
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+#[should_panic(expected = "fanout worker 1 panicked")]
+fn spawn_fanout_2() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let _: Vec<u32> = (0..10).sliding_windows(&mut storage)
+        .spawn_fanout(2, |window| if window[0] == 3 { panic!("bad window") } else { window[0] })
+        .collect();
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn par_map_windows_ordered_1() {
+    use std::thread;
+    use std::time::Duration;
+
+    let mut storage: Storage<u64> = Storage::new(3);
+    let mut results = (0..300u64).sliding_windows(&mut storage)
+        .par_map_windows_ordered(|window| {
+            // every fifth window is slow, the following ones finish first
+            if window[0] % 5 == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+            window.iter().sum::<u64>()
+        });
+    assert_eq!(results.size_hint(), (298, Some(298)));
+    let sums: Vec<u64> = results.by_ref().collect();
+    assert_eq!(sums, (0..298).map(|i| 3 * i + 3).collect::<Vec<u64>>());
+    assert_eq!(results.size_hint(), (0, Some(0)));
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+#[should_panic(expected = "bad window")]
+fn par_map_windows_ordered_2() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let mut results = (0..10).sliding_windows(&mut storage)
+        .par_map_windows_ordered(|window| if window[0] == 3 { panic!("bad window") } else { window[0] });
+    for expected in 0..3 {
+        assert_eq!(results.next(), Some(expected));
+    }
+    results.next();
+}
+
+#[test]
+fn record_1() {
+    let input = [1u32, 2, 3, 10, 11, 20, 21, 22, 23];
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut recorder = input.iter().cloned().sliding_windows(&mut storage)
+        .min_fill(2)
+        .reset_on_gap(|&x| x, 5)
+        .record();
+    let mut expected = Vec::new();
+    for window in recorder.by_ref() {
+        expected.push(window.iter().cloned().collect::<Vec<u32>>());
+    }
+    assert_eq!(expected, [vec![1, 2], vec![1, 2, 3], vec![10, 11], vec![20, 21], vec![20, 21, 22], vec![21, 22, 23]]);
+
+    let trace = recorder.into_trace();
+    assert_eq!(trace.elements(), &input[..]);
+    assert_eq!(trace.num_windows(), 6);
+    assert_eq!(trace.window(2), Some(&[10, 11][..]));
+
+    // the trace survives a roundtrip through its text format
+    let mut text = Vec::new();
+    trace.write_to(&mut text).unwrap();
+    assert!(text.starts_with(b"+ 1\n+ 2\n= 2\n+ 3\n= 3\n+ 10\n+ 11\n= 2\n"));
+    let read: Trace<u32> = Trace::read_from(&text[..]).unwrap();
+    assert_eq!(read, trace);
+
+    let mut storage = Storage::new(0);
+    let replayed: Vec<Vec<u32>> = read.replay(&mut storage).map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(replayed, expected);
+
+    let err = Trace::<u32>::read_from(&b"+ 1\n= 2\n"[..]).unwrap_err();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn record_2() {
+    // recording starts after windows were yielded or the adaptor was warmed up
+    let mut storage: Storage<u32> = Storage::new(3);
+    let mut windows = (0..6).sliding_windows(&mut storage);
+    assert_eq!(windows.next().unwrap(), &[0, 1, 2][..]);
+    let mut recorder = windows.record();
+    assert_eq!(recorder.next().unwrap(), &[1, 2, 3][..]);
+    let trace = recorder.into_trace();
+    assert_eq!(trace.window(0), Some(&[1, 2, 3][..]));
+
+    let mut replay_storage = Storage::new(0);
+    assert_eq!(trace.replay(&mut replay_storage).next().unwrap(), &[1, 2, 3][..]);
+
+    let mut windows = (0..6).sliding_windows(&mut storage);
+    windows.warmup();
+    let trace = windows.record().into_trace();
+    assert_eq!(trace.elements(), [0, 1]);
+}
+
+#[test]
+fn debug_validate_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    storage.debug_validate();
+    for len in 0..8 {
+        (0..len).sliding_windows(&mut storage).count();
+        storage.debug_validate();
+    }
+
+    // a storage reused for tumbling windows holds more elements than the window size
+    (0..10).tumbling_by(&mut storage, |&x| x / 5).count();
+    storage.debug_validate();
+}
+
+#[test]
+#[should_panic(expected = "storage invariant violated: a Window is still marked alive")]
+fn debug_validate_2() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    {
+        let mut windows = (0..5).sliding_windows(&mut storage);
+        ::std::mem::forget(windows.next());
+    }
+    storage.debug_validate();
+}
+
+#[test]
+fn windows_len_1() {
+    const LEN: usize = windows_len(10, 4, 1);
+    assert_eq!(LEN, 7);
+    assert_eq!(windows_len(10, 4, 3), 3);
+    assert_eq!(windows_len(3, 4, 1), 0);
+    assert_eq!(windows_len(10, 0, 1), 0);
+    assert_eq!(windows_len(0, 1, 1), 0);
+
+    // agrees with the adaptor
+    for len in 0..12 {
+        for step in 1..4 {
+            let mut storage: Storage<u32> = Storage::new(3);
+            let windows = (0..len as u32).sliding_windows(&mut storage).on_short(OnShort::Empty).emit_every(step);
+            assert_eq!(windows.size_hint(), (windows_len(len, 3, step), Some(windows_len(len, 3, step))));
+            assert_eq!(windows.count(), windows_len(len, 3, step));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "step must be positive")]
+fn windows_len_2() {
+    windows_len(3, 1, 0);
+}
+
+#[test]
+fn warmup_1() {
+    let mut storage: Storage<u32> = Storage::new(4);
+    let mut windows = (0..10).sliding_windows(&mut storage);
+    assert_eq!(windows.warmup(), 3);
+    assert_eq!(windows.warmup(), 0);
+    assert_eq!(windows.size_hint(), (7, Some(7)));
+    assert!(!windows.is_warm());
+    assert_eq!(windows.next().unwrap(), &[0, 1, 2, 3][..]);
+    assert!(windows.is_warm());
+    assert_eq!(windows.warmup(), 0);
+    assert_eq!(windows.count(), 6);
+
+    // min_fill shortens the warmup
+    let mut storage: Storage<u32> = Storage::new(4);
+    let mut windows = (0..10).sliding_windows(&mut storage).min_fill(2);
+    assert_eq!(windows.warmup(), 1);
+    assert_eq!(windows.next().unwrap(), &[0, 1][..]);
+    assert!(!windows.is_warm());
+}
+
+#[test]
+fn warmup_2() {
+    // the input ends during the warmup
+    let mut storage: Storage<u32> = Storage::new(4);
+    let mut windows = (0..2).sliding_windows(&mut storage);
+    assert_eq!(windows.warmup(), 2);
+    assert_eq!(windows.next().unwrap(), &[0, 1][..]);
+    assert!(windows.next().is_none());
+
+    let mut storage: Storage<u32> = Storage::new(4);
+    let mut windows = (0..2).sliding_windows(&mut storage).on_short(OnShort::Empty);
+    assert_eq!(windows.warmup(), 2);
+    assert!(windows.next().is_none());
+}
+
+#[test]
+fn poison_1() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    // panics in the input, in a gap key and in an element destructor all poison the storage
+    let mut storage: Storage<u32> = Storage::new(3);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..10).map(|x| if x == 5 { panic!("input") } else { x }).sliding_windows(&mut storage).count()
+    }));
+    assert!(result.is_err());
+    assert!(storage.is_poisoned());
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| (0..3).sliding_windows(&mut storage).count())).is_err());
+    storage.clear_poison();
+    assert!(!storage.is_poisoned());
+    assert_eq!((0..5).sliding_windows(&mut storage).count(), 3);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..10).sliding_windows(&mut storage)
+            .reset_on_gap(|&x| if x == 4 { panic!("key") } else { x }, 1)
+            .count()
+    }));
+    assert!(result.is_err());
+    assert!(storage.is_poisoned());
+
+    struct PanicOnDrop(u32);
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0 == 1 && !::std::thread::panicking() {
+                panic!("drop");
+            }
+        }
+    }
+    let mut storage: Storage<PanicOnDrop> = Storage::new(2);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..5).map(PanicOnDrop).sliding_windows(&mut storage).count()
+    }));
+    assert!(result.is_err());
+    assert!(storage.is_poisoned());
+    storage.clear_poison();
+
+    // a panic while using a window does not unwind through the adaptor
+    let mut storage: Storage<u32> = Storage::new(2);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        for window in (0..5).sliding_windows(&mut storage) {
+            assert!(*window.iter().next().unwrap() < 2, "consumer");
+        }
+    }));
+    assert!(result.is_err());
+    assert!(!storage.is_poisoned());
+}
+
+#[test]
+#[should_panic(expected = "storage poisoned by a panic in an earlier Adaptor")]
+fn poison_2() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mut storage: FixedStorage<u32, 2> = FixedStorage::new();
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..5).map(|x| if x == 3 { panic!("input") } else { x }).sliding_windows(&mut storage).count()
+    }));
+    assert!(storage.is_poisoned());
+    (0..5).sliding_windows(&mut storage).count();
+}
+
+#[test]
+fn poison_3() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    // usage errors don't poison the storage
+    let mut storage: Storage<u32> = Storage::new(3);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..2).sliding_windows(&mut storage).on_short(OnShort::Error).count()
+    }));
+    assert!(result.is_err());
+    assert!(!storage.is_poisoned());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut windows = (0..5).sliding_windows(&mut storage);
+        let _window = windows.next();
+        windows.next().map(|w| w.fill())
+    }));
+    assert!(result.is_err());
+    assert!(!storage.is_poisoned());
+    assert_eq!((0..5).sliding_windows(&mut storage).count(), 3);
+}
+
+#[test]
+fn unwind_safe_1() {
+    use std::panic::{RefUnwindSafe, UnwindSafe};
+
+    fn unwind_safe<T: UnwindSafe>() {}
+    fn ref_unwind_safe<T: RefUnwindSafe>() {}
+
+    unwind_safe::<Storage<u32>>();
+    ref_unwind_safe::<Storage<u32>>();
+    ref_unwind_safe::<ArrayStorage<u32, 3>>();
+    ref_unwind_safe::<FixedStorage<u32, 3>>();
+    ref_unwind_safe::<Window<'static, u32>>();
+}
+
+#[test]
+fn fold_with_index_1() {
+    let mut storage: Storage<u32> = Storage::new(4);
+    for (n, window) in (0..10).sliding_windows(&mut storage).enumerate() {
+        // the logical positions are contiguous regardless of the rotation of the ring
+        let visited = window.fold_with_index(Vec::new(), |mut acc, i, &x| {
+            acc.push((i, x));
+            acc
+        });
+        let expected: Vec<(usize, u32)> = (0..4).map(|i| (i, (n + i) as u32)).collect();
+        assert_eq!(visited, expected);
+    }
+
+    let mut storage: Storage<u32> = Storage::new(4);
+    let window = (0..2).sliding_windows(&mut storage).next().unwrap();
+    assert_eq!(window.fold_with_index(0, |acc, i, &x| acc + i as u32 * x), 1);
+}
+
+#[test]
+fn sliding_windows_map_1() {
+    let calls = Cell::new(0);
+    let mut storage: Storage<u32> = Storage::new(3);
+    let maxima: Vec<u32> = ["3", "1", "4", "1", "5"].iter()
+        .sliding_windows_map(&mut storage, |s| {
+            calls.set(calls.get() + 1);
+            s.parse().unwrap()
+        })
+        .map(|window| *window.iter().max().unwrap())
+        .collect();
+
+    assert_eq!(maxima, [4, 4, 5]);
+    assert_eq!(calls.get(), 5);
+}
+
+#[test]
+fn sliding_windows_step_1() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let windows: Vec<Vec<u32>> = (0..18).sliding_windows_step(&mut storage, 5)
+        .map(|window| window.iter().cloned().collect())
+        .collect();
+    assert_eq!(windows, [[0, 1], [5, 6], [10, 11], [15, 16]]);
+
+    // elements between the windows are never pushed
+    let mut storage = CountingStorage { inner: Storage::new(2), pushes: Cell::new(0), period: 1000 };
+    let count = Adaptor::new(0..18, &mut storage).emit_every(5).count();
+    assert_eq!(count, 4);
+    assert_eq!(storage.pushes.get(), 8);
+
+    // decay only touches the elements of the next window
+    let mut storage: Storage<u32> = Storage::new(3);
+    let windows: Vec<Vec<u32>> = (0..12).sliding_windows(&mut storage)
+        .with_decay(|x| *x += 100)
+        .emit_every(4)
+        .map(|window| window.iter().cloned().collect())
+        .collect();
+    assert_eq!(windows, [vec![0, 1, 2], vec![204, 105, 6], vec![208, 109, 10]]);
+}
+
+#[test]
+#[should_panic(expected = "step must be positive")]
+fn sliding_windows_step_2() {
+    let mut storage: Storage<u32> = Storage::new(2);
+    let _ = (0..4).sliding_windows_step(&mut storage, 0);
+}
+
+#[test]
+fn windowed_view_1() {
+    let data: Vec<u32> = (0..20).map(|x| x * x).collect();
+    let mut storage: Storage<u32> = Storage::new(4);
+    let expected: Vec<Vec<u32>> = data.windows(4).map(|w| w.to_vec()).collect();
+
+    let mut view = data.iter().cloned().windowed_view(&mut storage);
+    assert_eq!(view.len(), expected.len());
+    // in any order
+    for &i in &[16, 0, 7, 7, 3] {
+        let window: Vec<u32> = view.window_at(i).unwrap().iter().cloned().collect();
+        assert_eq!(window, expected[i]);
+    }
+    assert!(view.window_at(17).is_none());
+
+    let view = (0..3).windowed_view(&mut storage);
+    assert!(view.is_empty());
+}
+
+#[test]
+fn partition_point_windows_1() {
+    let data: Vec<u32> = vec![1, 2, 2, 5, 7, 7, 9, 12, 20];
+    let mut storage: Storage<u32> = Storage::new(2);
+    let maxima: Vec<u32> = data.windows(2).map(|w| w[1]).collect();
+
+    let mut view = data.iter().cloned().windowed_view(&mut storage);
+    for threshold in 0..22 {
+        let evaluated = Cell::new(0);
+        let index = view.partition_point_windows(|w| {
+            evaluated.set(evaluated.get() + 1);
+            *w.iter().max().unwrap() < threshold
+        });
+        assert_eq!(index, maxima.partition_point(|&max| max < threshold));
+        assert!(evaluated.get() <= 4);
+    }
+
+    let mut empty = (0..1).windowed_view(&mut storage);
+    assert_eq!(empty.partition_point_windows(|_| true), 0);
+}
+
+#[test]
+fn tumbling_windows_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let windows = (0..9).tumbling_windows(&mut storage);
+    assert_eq!(windows.size_hint(), (3, Some(3)));
+    let output: Vec<Vec<u32>> = windows.map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, [[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+
+    // the trailing chunk is dropped
+    assert_eq!((0..8).tumbling_windows(&mut storage).count(), 2);
+
+    let mut empty: Storage<u32> = Storage::new(0);
+    assert_eq!((0..8).tumbling_windows(&mut empty).count(), 0);
+}
+
+#[test]
+fn sliding_windows_paired_1() {
+    let calls = Cell::new(0);
+    let mut storage: Storage<(u32, u64)> = Storage::new(3);
+    let mut windows = (1..6).sliding_windows_paired(&mut storage, |&x| {
+        calls.set(calls.get() + 1);
+        (x as u64).pow(2)
+    });
+
+    let window = windows.next().unwrap();
+    assert_eq!(window.originals().cloned().collect::<Vec<u32>>(), [1, 2, 3]);
+    assert_eq!(window.derived().cloned().collect::<Vec<u64>>(), [1, 4, 9]);
+    drop(window);
+
+    let last = windows.map(|window| window.derived().sum::<u64>()).last();
+    assert_eq!(last, Some(9 + 16 + 25));
+    assert_eq!(calls.get(), 5);
+}
+
+#[test]
+fn pad_with_1() {
+    let mut storage: Storage<u32> = Storage::new(3);
+    let windows = (1..4).sliding_windows(&mut storage).pad_with(0);
+    assert_eq!(windows.size_hint(), (5, Some(5)));
+    let output: Vec<Vec<u32>> = windows.map(|w| w.iter().cloned().collect()).collect();
+    assert_eq!(output, [vec![0, 0, 1], vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 0], vec![3, 0, 0]]);
+
+    // framing with a step, every window is full
+    let fills: Vec<usize> = (1..6).sliding_windows(&mut storage).pad_with(0).emit_every(2)
+        .map(|w| w.fill())
+        .collect();
+    assert_eq!(fills, [3, 3, 3, 3]);
+
+    // an empty input is not padded
+    assert_eq!((0..0).sliding_windows(&mut storage).pad_with(0).count(), 0);
+}
+
+#[test]
+fn partial_suffix_1() {
+    fn windows(len: u32, size: usize, step: usize) -> Vec<Vec<u32>> {
+        let mut storage: Storage<u32> = Storage::new(size);
+        let mut windows = (0..len).sliding_windows(&mut storage).emit_every(step).with_partial_suffix(true);
+        let mut output = Vec::new();
+        loop {
+            let (lower, upper) = windows.size_hint();
+            match windows.next() {
+                Some(window) => {
+                    assert!(upper.unwrap() >= 1);
+                    output.push(window.iter().cloned().collect());
+                }
+                None => {
+                    assert_eq!(lower, 0);
+                    break;
+                }
+            }
+        }
+        assert_eq!(windows.size_hint(), (0, Some(0)));
+        output
+    }
+
+    assert_eq!(windows(5, 3, 1), [vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4], vec![3, 4], vec![4]]);
+    // a short input yields the partial window and its suffixes
+    assert_eq!(windows(2, 3, 1), [vec![0, 1], vec![1]]);
+    assert_eq!(windows(0, 3, 1), Vec::<Vec<u32>>::new());
+    // trailing windows continue the step
+    assert_eq!(windows(10, 5, 2), [vec![0, 1, 2, 3, 4], vec![2, 3, 4, 5, 6], vec![4, 5, 6, 7, 8], vec![6, 7, 8, 9], vec![8, 9]]);
+    assert_eq!(windows(9, 2, 3), [vec![0, 1], vec![3, 4], vec![6, 7]]);
+    assert_eq!(windows(8, 2, 3), [vec![0, 1], vec![3, 4], vec![6, 7]]);
+    assert_eq!(windows(7, 2, 3), [vec![0, 1], vec![3, 4], vec![6]]);
+
+    // every window starting within the input is yielded
+    for len in 0..12 {
+        for size in 1..5 {
+            for step in 1..4 {
+                let starts: Vec<u32> = windows(len, size, step).iter().map(|w| w[0]).collect();
+                let expected: Vec<u32> = (0..len).step_by(step).collect();
+                assert_eq!(starts, expected, "len {}, size {}, step {}", len, size, step);
+            }
+        }
+    }
+}
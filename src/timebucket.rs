@@ -0,0 +1,115 @@
+/// What [TimeBuckets](struct.TimeBuckets.html) yields for an interval without elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmptyBucket<U> {
+    /// Yield nothing, so consecutive outputs may be more than one interval apart.
+    Skip,
+    /// Yield a constant, e.g. zero for counts.
+    Fill(U),
+    /// Yield the value of the previous bucket again.
+    Repeat,
+}
+
+/// Iterator aggregating a timestamped stream into one value per fixed interval.
+///
+/// See [IterExt::time_buckets](trait.IterExt.html#method.time_buckets) for more information.
+pub struct TimeBuckets<I: Iterator, F, R, U> {
+    iter: I,
+    interval: u64,
+    timestamp: F,
+    reducer: R,
+    empty: EmptyBucket<U>,
+    buffer: Vec<I::Item>,
+    // the first element of the next bucket and its bucket index
+    pending: Option<(u64, I::Item)>,
+    // the index of the last yielded bucket
+    last_bucket: Option<u64>,
+    last_value: Option<U>,
+}
+
+impl<I: Iterator, U: Clone, F, R> TimeBuckets<I, F, R, U>
+    where F: FnMut(&I::Item) -> u64, R: FnMut(&[I::Item]) -> U
+{
+    /// This creates a new TimeBuckets iterator. Usually you should be using
+    /// [IterExt::time_buckets](trait.IterExt.html#method.time_buckets).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `interval` is 0.
+    pub fn new(iter: I, interval: u64, timestamp: F, reducer: R, empty: EmptyBucket<U>) -> TimeBuckets<I, F, R, U> {
+        assert!(interval > 0, "interval must be positive");
+
+        TimeBuckets {
+            iter,
+            interval,
+            timestamp,
+            reducer,
+            empty,
+            buffer: Vec::new(),
+            pending: None,
+            last_bucket: None,
+            last_value: None,
+        }
+    }
+
+    fn bucket(&mut self, elt: &I::Item) -> u64 {
+        let bucket = (self.timestamp)(elt) / self.interval;
+        if let Some(last) = self.last_bucket {
+            assert!(bucket >= last, "timestamps must be non-decreasing");
+        }
+        bucket
+    }
+}
+
+impl<I: Iterator, U: Clone, F, R> Iterator for TimeBuckets<I, F, R, U>
+    where F: FnMut(&I::Item) -> u64, R: FnMut(&[I::Item]) -> U
+{
+    /// The start of the interval and the aggregated value.
+    type Item = (u64, U);
+
+    fn next(&mut self) -> Option<(u64, U)> {
+        let (bucket, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let elt = self.iter.next()?;
+                (self.bucket(&elt), elt)
+            },
+        };
+
+        // fill the intervals between the last bucket and this one
+        if let Some(last) = self.last_bucket {
+            if bucket > last + 1 {
+                let fill = match self.empty {
+                    EmptyBucket::Skip => None,
+                    EmptyBucket::Fill(ref value) => Some(value.clone()),
+                    EmptyBucket::Repeat => self.last_value.clone(),
+                };
+
+                if let Some(fill) = fill {
+                    self.pending = Some((bucket, first));
+                    self.last_bucket = Some(last + 1);
+                    return Some(((last + 1) * self.interval, fill));
+                }
+            }
+        }
+
+        // the buffer is reused, so this doesn't allocate once it is large enough
+        self.buffer.clear();
+        self.buffer.push(first);
+        self.last_bucket = Some(bucket);
+
+        while let Some(elt) = self.iter.next() {
+            let next_bucket = self.bucket(&elt);
+            if next_bucket != bucket {
+                self.pending = Some((next_bucket, elt));
+                break;
+            }
+            self.buffer.push(elt);
+        }
+
+        let value = (self.reducer)(&self.buffer);
+        if let EmptyBucket::Repeat = self.empty {
+            self.last_value = Some(value.clone());
+        }
+        Some((bucket * self.interval, value))
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use ring::Ring;
+
+/// Iterator yielding the `k` largest elements by key of every window.
+///
+/// See [IterExt::rolling_top_k_by_key](trait.IterExt.html#method.rolling_top_k_by_key) for more information.
+pub struct RollingTopK<I: Iterator, K, F> {
+    iter: I,
+    k: usize,
+    key: F,
+    // the keys and sequence numbers of the elements in the window, in input order
+    window: Ring<(K, u64)>,
+    // all elements of the window ordered by key, ties ordered by their sequence number
+    sorted: BTreeMap<(K, u64), I::Item>,
+    seq: u64,
+}
+
+impl<I: Iterator, K, F> RollingTopK<I, K, F> where K: Ord + Clone, F: FnMut(&I::Item) -> K {
+    /// This creates a new RollingTopK iterator. Usually you should be using
+    /// [IterExt::rolling_top_k_by_key](trait.IterExt.html#method.rolling_top_k_by_key).
+    pub fn new(iter: I, window_size: usize, k: usize, key: F) -> RollingTopK<I, K, F> {
+        RollingTopK {
+            iter,
+            k,
+            key,
+            window: Ring::new(window_size),
+            sorted: BTreeMap::new(),
+            seq: 0,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for RollingTopK<I, K, F>
+    where I::Item: Clone, K: Ord + Clone, F: FnMut(&I::Item) -> K
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+
+        for elt in &mut self.iter {
+            let entry = ((self.key)(&elt), self.seq);
+            self.seq += 1;
+
+            if let Some(evicted) = self.window.push(entry.clone()) {
+                self.sorted.remove(&evicted);
+            }
+            self.sorted.insert(entry, elt);
+
+            if self.window.is_full() {
+                return Some(self.sorted.values().rev().take(self.k).cloned().collect());
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.windows_hint(self.iter.size_hint())
+    }
+}
@@ -0,0 +1,79 @@
+use sliding_windows::{Storage, Window};
+
+/// Iterator yielding one `Window` per run of consecutive elements with the same bucket key.
+///
+/// See [IterExt::tumbling_by](trait.IterExt.html#method.tumbling_by) for more information.
+pub struct TumblingBy<'a, I: Iterator, K, F> where I::Item: 'a {
+    iter: I,
+    bucket: F,
+    storage: &'a Storage<I::Item>,
+    // the first element of the next bucket, read while flushing the current one
+    pending: Option<(K, I::Item)>,
+    done: bool,
+}
+
+impl<'a, I: Iterator, K: PartialEq, F> TumblingBy<'a, I, K, F> where F: FnMut(&I::Item) -> K {
+    /// This creates a new TumblingBy iterator. Usually you should be using
+    /// [IterExt::tumbling_by](trait.IterExt.html#method.tumbling_by).
+    pub fn new(iter: I, storage: &'a mut Storage<I::Item>, bucket: F) -> TumblingBy<'a, I, K, F> {
+        // in case the storage was reused
+        storage.clear_shared();
+
+        TumblingBy {
+            iter,
+            bucket,
+            storage,
+            pending: None,
+            done: false,
+        }
+    }
+}
+
+impl<'a, I: Iterator, K: PartialEq, F> Iterator for TumblingBy<'a, I, K, F> where F: FnMut(&I::Item) -> K {
+    type Item = (K, Window<'a, I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => match self.iter.next() {
+                Some(elt) => ((self.bucket)(&elt), elt),
+                None => {
+                    self.done = true;
+                    return None;
+                },
+            },
+        };
+
+        self.storage.clear_shared();
+        self.storage.push_unbounded(first);
+        self.done = true;
+
+        for elt in &mut self.iter {
+            let next_key = (self.bucket)(&elt);
+            if next_key != key {
+                self.pending = Some((next_key, elt));
+                self.done = false;
+                break;
+            }
+            self.storage.push_unbounded(elt);
+        }
+
+        let window = self.storage.window_with_capacity(self.storage.len());
+        Some((key, window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let pending = self.pending.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+        // every bucket holds at least one element
+        ((lower > 0 || pending > 0) as usize, upper.map(|upper| upper + pending))
+    }
+}
@@ -0,0 +1,84 @@
+use sliding_windows::{windows_len, AsWindowStorage, Storage, Window};
+
+/// Random access to the full windows over a source which is cheap to clone, like a range or
+/// a slice iterator.
+///
+/// See [IterExt::windowed_view](trait.IterExt.html#method.windowed_view) for more information.
+pub struct WindowedView<'a, I: Iterator> where I::Item: 'a {
+    iter: I,
+    storage: &'a mut Storage<I::Item>,
+}
+
+impl<'a, I: ExactSizeIterator + Clone> WindowedView<'a, I> {
+    /// This creates a new WindowedView. Usually you should be using
+    /// [IterExt::windowed_view](trait.IterExt.html#method.windowed_view).
+    pub fn new(iter: I, storage: &'a mut Storage<I::Item>) -> WindowedView<'a, I> {
+        storage.assert_not_poisoned();
+        WindowedView {
+            iter,
+            storage,
+        }
+    }
+
+    /// The number of full windows, see [windows_len](fn.windows_len.html).
+    pub fn len(&self) -> usize {
+        windows_len(self.iter.len(), self.storage.window_size(), 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `i`th window, or `None` if there are not more than `i` full windows.
+    ///
+    /// The window is collected from a clone of the source which skips the first `i`
+    /// elements, so this takes `O(i + window_size)` for general iterators, but only
+    /// `O(window_size)` for sources with a fast `nth`, like ranges and slice iterators.
+    pub fn window_at(&mut self, i: usize) -> Option<Window<'_, I::Item>> {
+        if i >= self.len() {
+            return None;
+        }
+
+        self.storage.clear_shared();
+        let window_size = self.storage.window_size();
+        let mut iter = self.iter.clone();
+        if i > 0 {
+            iter.nth(i - 1);
+        }
+        for elt in iter.take(window_size) {
+            self.storage.push(elt);
+        }
+        Some(self.storage.new_window())
+    }
+
+    /// The index of the first window for which `pred` is false, assuming `pred` is true for
+    /// all windows before it and false for all windows after it, like
+    /// `slice::partition_point`. Returns [len](#method.len) if `pred` holds for every window.
+    ///
+    /// Only `O(log(len))` windows are evaluated, e.g. to find the first window whose score
+    /// crosses a threshold in a series where the score grows monotonically.
+    ///
+    /// ```
+    /// use sliding_windows::{IterExt, Storage};
+    ///
+    /// let mut storage: Storage<u32> = Storage::new(10);
+    /// let mut view = (0..1_000_000u32).windowed_view(&mut storage);
+    ///
+    /// let first = view.partition_point_windows(|w| w.iter().sum::<u32>() < 1_000_000);
+    /// assert_eq!(first, 99_996);
+    /// ```
+    pub fn partition_point_windows<F>(&mut self, mut pred: F) -> usize where F: FnMut(&Window<'_, I::Item>) -> bool {
+        // pred holds before `lower` and doesn't hold from `upper` on
+        let (mut lower, mut upper) = (0, self.len());
+        while lower < upper {
+            let mid = lower + (upper - lower) / 2;
+            let window = self.window_at(mid).expect("mid is less than len");
+            if pred(&window) {
+                lower = mid + 1;
+            } else {
+                upper = mid;
+            }
+        }
+        lower
+    }
+}
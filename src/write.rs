@@ -0,0 +1,81 @@
+use std::io::{self, Write};
+
+#[cfg(feature = "csv")]
+use csv::WriterBuilder;
+use serde::{Serialize, Serializer};
+
+use sliding_windows::{Adaptor, AsWindowStorage, Window};
+
+/// Serializes the elements of the window in logical order as a sequence.
+///
+/// Only available with the `serde` or `csv` feature.
+impl<'a, T: Serialize> Serialize for Window<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+/// The output format of [Adaptor::write_windows](struct.Adaptor.html#method.write_windows).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowFormat {
+    /// One CSV record per window without a header, the elements must serialize to CSV fields.
+    /// Requires the `csv` feature.
+    #[cfg(feature = "csv")]
+    Csv,
+    /// One JSON array per window, each followed by a newline.
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    JsonLines,
+}
+
+impl<'a, I: Iterator, S: AsWindowStorage<I::Item>> Adaptor<'a, I, S> where I::Item: Serialize {
+    /// Consumes the adaptor and serializes every window in logical order into `writer`,
+    /// without collecting the windows first. Returns the number of windows written.
+    ///
+    /// Only available with the `serde` or `csv` feature.
+    ///
+    /// ```
+    /// # extern crate sliding_windows;
+    /// use sliding_windows::{IterExt, Storage, WindowFormat};
+    ///
+    /// # fn main() {
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// let mut storage: Storage<u32> = Storage::new(3);
+    /// let mut output = Vec::new();
+    /// let count = (0..5).sliding_windows(&mut storage).write_windows(&mut output, WindowFormat::JsonLines).unwrap();
+    ///
+    /// assert_eq!(count, 3);
+    /// assert_eq!(output, b"[0,1,2]\n[1,2,3]\n[2,3,4]\n");
+    /// # }
+    /// # }
+    /// ```
+    pub fn write_windows<W: Write>(self, writer: W, format: WindowFormat) -> io::Result<usize> {
+        let mut count = 0;
+
+        match format {
+            #[cfg(feature = "csv")]
+            WindowFormat::Csv => {
+                // windows at the end of the input may be shorter, so records may differ in length
+                let mut csv = WriterBuilder::new().has_headers(false).flexible(true).from_writer(writer);
+                for window in self {
+                    csv.serialize(&window)?;
+                    count += 1;
+                }
+                csv.flush()?;
+            },
+            #[cfg(feature = "serde")]
+            WindowFormat::JsonLines => {
+                let mut writer = writer;
+                for window in self {
+                    serde_json::to_writer(&mut writer, &window)?;
+                    writer.write_all(b"\n")?;
+                    count += 1;
+                }
+                writer.flush()?;
+            },
+        }
+
+        Ok(count)
+    }
+}